@@ -0,0 +1,166 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+static BOOKMARKS_FILE: &str = "rusty-calc-bookmarks.json";
+
+static BOOKMARK_MANAGER: LazyLock<BookmarkManager> = LazyLock::new(|| -> BookmarkManager {
+    let bookmarks = match load_from_file(get_bookmarks_path()) {
+        Ok(bookmarks) => bookmarks,
+        Err(e) => {
+            warn!("Unable to open bookmarks file: {}", e);
+            info!("A new bookmarks file will be created");
+            Bookmarks::default()
+        }
+    };
+
+    BookmarkManager { bookmarks }
+});
+
+/// Bookmarks are expressions the user wants to keep for reuse. Unlike history they are only
+/// added and removed at the user's request.
+pub struct BookmarkManager {
+    bookmarks: Bookmarks,
+}
+
+impl BookmarkManager {
+    fn save_to_file(&self, path: PathBuf) -> io::Result<()> {
+        let serialized = serde_json::to_string(&self.bookmarks)?;
+        let mut file = File::create(&path)?;
+        file.write_all(serialized.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = self.save_to_file(get_bookmarks_path()) {
+            warn!("{}", e.to_string());
+            warn!("Failed to write bookmarks.")
+        }
+    }
+
+    pub fn add(&self, expression: &str) {
+        self.bookmarks.add(expression);
+    }
+
+    pub fn remove(&self, expression: &str) {
+        self.bookmarks.remove(expression);
+    }
+
+    pub fn bookmarks(&self) -> &Bookmarks {
+        &self.bookmarks
+    }
+}
+
+pub fn manager() -> &'static BookmarkManager {
+    &BOOKMARK_MANAGER
+}
+
+fn get_bookmarks_path() -> PathBuf {
+    paths::data_file(BOOKMARKS_FILE)
+}
+
+fn load_from_file(path: PathBuf) -> io::Result<Bookmarks> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Bookmarks {
+    entries: RwLock<Vec<String>>,
+}
+
+impl Bookmarks {
+
+    fn add(&self, expression: &str) {
+        let expression = expression.trim();
+        if expression.is_empty() {
+            return;
+        }
+        match self.entries.write() {
+            Ok(mut vec) => {
+                if !vec.iter().any(|e| e == expression) {
+                    vec.push(expression.to_string());
+                }
+            }
+            Err(_) => {
+                warn!("Failed to write bookmarks.")
+            }
+        }
+    }
+
+    fn remove(&self, expression: &str) {
+        match self.entries.write() {
+            Ok(mut vec) => {
+                vec.retain(|e| e != expression.trim());
+            }
+            Err(_) => {
+                warn!("Failed to write bookmarks.")
+            }
+        }
+    }
+
+    pub fn entries(&self) -> &RwLock<Vec<String>> {
+        &self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::bookmarks::{BookmarkManager, Bookmarks, load_from_file};
+
+    #[test]
+    fn test_add_remove() {
+        let bookmarks = Bookmarks::default();
+        bookmarks.add("sqrt(3^2 + 4^2)");
+        bookmarks.add("2 * π * 6371");
+        bookmarks.add(" sqrt(3^2 + 4^2)\n");
+        bookmarks.add("");
+        assert_eq!(*bookmarks.entries().read().unwrap(), vec!["sqrt(3^2 + 4^2)", "2 * π * 6371"]);
+
+        bookmarks.remove("sqrt(3^2 + 4^2)");
+        assert_eq!(*bookmarks.entries().read().unwrap(), vec!["2 * π * 6371"]);
+    }
+
+    #[test]
+    fn test_persist() {
+        let manager = BookmarkManager { bookmarks: Bookmarks::default() };
+        manager.add("2 * π * 6371");
+        manager.add("exp(1)");
+
+        let path = std::env::temp_dir().join("rusty-calc-bookmarks-test.json");
+        manager.save_to_file(path.clone()).unwrap();
+        let loaded = load_from_file(path.clone()).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(*loaded.entries().read().unwrap(), vec!["2 * π * 6371", "exp(1)"]);
+    }
+}
@@ -0,0 +1,154 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use std::f64::consts::PI;
+
+use crate::conversions::{convert, Dimension, System, Unit};
+use crate::evaluator::AngleMode;
+
+// Angle unit constants
+// The radian is the base. Degrees use the symbol `deg`, as `°` after a number starts an angle in
+// degrees, minutes and seconds.
+pub static RADIAN: Unit = Unit {
+    name: "Radian",
+    symbol: "rad",
+    dimension: Dimension::Angle,
+    system: System::Metric,
+    to_base: None,
+    from_base: None,
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static DEGREE: Unit = Unit {
+    name: "Degree",
+    symbol: "deg",
+    dimension: Dimension::Angle,
+    system: System::Metric,
+    to_base: Some(f64::to_radians),
+    from_base: Some(f64::to_degrees),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static GRADIAN: Unit = Unit {
+    name: "Gradian",
+    symbol: "grad",
+    dimension: Dimension::Angle,
+    system: System::Metric,
+    to_base: Some(|v| v * PI / 200.0),
+    from_base: Some(|v| v * 200.0 / PI),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static ARCMINUTE: Unit = Unit {
+    name: "Arcminute",
+    symbol: "arcmin",
+    dimension: Dimension::Angle,
+    system: System::Metric,
+    to_base: Some(|v| (v / 60.0).to_radians()),
+    from_base: Some(|v| v.to_degrees() * 60.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static ARCSECOND: Unit = Unit {
+    name: "Arcsecond",
+    symbol: "arcsec",
+    dimension: Dimension::Angle,
+    system: System::Metric,
+    to_base: Some(|v| (v / 3600.0).to_radians()),
+    from_base: Some(|v| v.to_degrees() * 3600.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static TURN: Unit = Unit {
+    name: "Turn",
+    symbol: "turn",
+    dimension: Dimension::Angle,
+    system: System::Metric,
+    to_base: Some(|v| v * 2.0 * PI),
+    from_base: Some(|v| v / (2.0 * PI)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+
+pub(crate) fn get_all() -> Vec<&'static Unit> {
+    vec![&DEGREE, &RADIAN, &GRADIAN, &ARCMINUTE, &ARCSECOND, &TURN]
+}
+
+/// The unit angles are measured in for an angle mode.
+pub(crate) fn mode_unit(mode: &AngleMode) -> &'static Unit {
+    match mode {
+        AngleMode::Degrees => &DEGREE,
+        AngleMode::Radians => &RADIAN,
+        AngleMode::Gradians => &GRADIAN,
+    }
+}
+
+/// Take an angle in the units of the angle mode and give it in the units of the other two modes,
+/// like the DRG→ key of a scientific calculator.
+pub(crate) fn in_other_modes(value: f64, mode: &AngleMode) -> Vec<(&'static Unit, f64)> {
+    let from = mode_unit(mode);
+    [&DEGREE, &RADIAN, &GRADIAN].into_iter()
+        .filter(|unit| *unit != from)
+        .map(|unit| (unit, convert(&value, from, unit)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use crate::assert_near;
+    use crate::conversions::angle::{ARCMINUTE, ARCSECOND, DEGREE, GRADIAN, in_other_modes, RADIAN, TURN};
+    use crate::conversions::convert;
+    use crate::evaluator::AngleMode;
+
+    #[test]
+    fn test_angle_units() {
+        assert_near!(convert(&180.0, &DEGREE, &RADIAN), PI);
+        assert_near!(convert(&100.0, &GRADIAN, &DEGREE), 90.0);
+        assert_near!(convert(&1.0, &DEGREE, &ARCMINUTE), 60.0);
+        assert_near!(convert(&1.0, &ARCMINUTE, &ARCSECOND), 60.0);
+        assert_near!(convert(&0.25, &TURN, &DEGREE), 90.0);
+    }
+
+    #[test]
+    fn test_in_other_modes() {
+        let converted = in_other_modes(90.0, &AngleMode::Degrees);
+        assert_eq!(converted.len(), 2);
+        assert_eq!(converted[0].0, &RADIAN);
+        assert_near!(converted[0].1, PI / 2.0);
+        assert_eq!(converted[1].0, &GRADIAN);
+        assert_near!(converted[1].1, 100.0);
+
+        let converted = in_other_modes(PI, &AngleMode::Radians);
+        assert_eq!(converted[0].0, &DEGREE);
+        assert_near!(converted[0].1, 180.0);
+        assert_eq!(converted[1].0, &GRADIAN);
+        assert_near!(converted[1].1, 200.0);
+    }
+}
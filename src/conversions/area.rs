@@ -29,115 +29,139 @@ use crate::conversions::length::*;
 
 pub static SQ_METRE: Unit = Unit {
     name: "Sq_Metre",
+    symbol: "m²",
     dimension: Dimension::Area,
     system: System::Metric,
     to_base: None,
     from_base: None,
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static SQ_CENTIMETRE: Unit = Unit {
     name: "Sq_Centimetre",
+    symbol: "cm²",
     dimension: Dimension::Area,
     system: System::Metric,
     to_base: Some(|v| CENTIMETRE.to_base.unwrap()(CENTIMETRE.to_base.unwrap()(v))),
     from_base: Some(|v| CENTIMETRE.from_base.unwrap()(CENTIMETRE.from_base.unwrap()(v))),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static SQ_MILLIMETRE: Unit = Unit {
     name: "Sq_Millimetre",
+    symbol: "mm²",
     dimension: Dimension::Area,
     system: System::Metric,
     to_base: Some(|v| MILLIMETRE.to_base.unwrap()(MILLIMETRE.to_base.unwrap()(v))),
     from_base: Some(|v| MILLIMETRE.from_base.unwrap()(MILLIMETRE.from_base.unwrap()(v))),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static SQ_MICROMETRE: Unit = Unit {
     name: "Sq_Micrometre",
+    symbol: "µm²",
     dimension: Dimension::Area,
     system: System::Metric,
     to_base: Some(|v| MICROMETRE.to_base.unwrap()(MICROMETRE.to_base.unwrap()(v))),
     from_base: Some(|v| MICROMETRE.from_base.unwrap()(MICROMETRE.from_base.unwrap()(v))),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static HECTARE: Unit = Unit {
     name: "Hectare",
+    symbol: "ha",
     dimension: Dimension::Area,
     system: System::Metric,
     to_base: Some(|v| v * 10000.0),
     from_base: Some(|v| v / 10000.0),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 
 pub static SQ_KILOMETRE: Unit = Unit {
     name: "Sq_Kilometre",
+    symbol: "km²",
     dimension: Dimension::Area,
     system: System::Metric,
     to_base: Some(|v| KILOMETRE.to_base.unwrap()(KILOMETRE.to_base.unwrap()(v))),
     from_base: Some(|v| KILOMETRE.from_base.unwrap()(KILOMETRE.from_base.unwrap()(v))),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 
 // Imperial units
 
 pub static SQ_YARD: Unit = Unit {
     name: "Sq_Yard",
+    symbol: "yd²",
     dimension: Dimension::Area,
     system: System::Imperial,
     to_base: Some(|v| YARD.to_base.unwrap()(YARD.to_base.unwrap()(v))),
     from_base: Some(|v| YARD.from_base.unwrap()(YARD.from_base.unwrap()(v))),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
 };
 pub static SQ_FOOT: Unit = Unit {
     name: "Sq_Foot",
+    symbol: "ft²",
     dimension: Dimension::Area,
     system: System::Imperial,
     to_base: Some(|v| FOOT.to_base.unwrap()(FOOT.to_base.unwrap()(v))),
     from_base: Some(|v| FOOT.from_base.unwrap()(FOOT.from_base.unwrap()(v))),
-    to_system_base: Some(|v| FOOT.to_system_base.unwrap()(FOOT.to_system_base.unwrap()(v))),
-    from_system_base: Some(|v| FOOT.from_system_base.unwrap()(FOOT.from_system_base.unwrap()(v))),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 9.0)),
 };
 pub static SQ_INCH: Unit = Unit {
     name: "Sq_Inch",
+    symbol: "in²",
     dimension: Dimension::Area,
     system: System::Imperial,
     to_base: Some(|v| INCH.to_base.unwrap()(INCH.to_base.unwrap()(v))),
     from_base: Some(|v| INCH.from_base.unwrap()(INCH.from_base.unwrap()(v))),
-    to_system_base: Some(|v| INCH.to_system_base.unwrap()(INCH.to_system_base.unwrap()(v))),
-    from_system_base: Some(|v| INCH.from_system_base.unwrap()(INCH.from_system_base.unwrap()(v))),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 36.0 * 36.0)),
 };
 pub static ACRE: Unit = Unit {
     name: "Acre",
+    symbol: "ac",
     dimension: Dimension::Area,
     system: System::Imperial,
     to_base: Some(|v| v * 4046.856422),
     from_base: Some(|v| v / 4046.856422),
-    to_system_base: Some(|v| v * 4840.0),
-    from_system_base: Some(|v| v / 4840.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((4840.0, 1.0)),
 };
 pub static SQ_MILE: Unit = Unit {
     name: "Sq_Mile",
+    symbol: "mi²",
     dimension: Dimension::Area,
     system: System::Imperial,
     to_base: Some(|v| MILE.to_base.unwrap()(MILE.to_base.unwrap()(v))),
     from_base: Some(|v| MILE.from_base.unwrap()(MILE.from_base.unwrap()(v))),
-    to_system_base: Some(|v| MILE.to_system_base.unwrap()(MILE.to_system_base.unwrap()(v))),
-    from_system_base: Some(|v| MILE.from_system_base.unwrap()(MILE.from_system_base.unwrap()(v))),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1760.0 * 1760.0, 1.0)),
 };
 pub static SQ_NAUTICAL_MILE: Unit = Unit {
     name: "Sq_Nm",
+    symbol: "nmi²",
     dimension: Dimension::Area,
     system: System::Imperial,
     to_base: Some(|v| NAUTICAL_MILE.to_base.unwrap()(NAUTICAL_MILE.to_base.unwrap()(v))),
     from_base: Some(|v| NAUTICAL_MILE.from_base.unwrap()(NAUTICAL_MILE.from_base.unwrap()(v))),
-    to_system_base: Some(|v| NAUTICAL_MILE.to_system_base.unwrap()(NAUTICAL_MILE.to_system_base.unwrap()(v))),
-    from_system_base: Some(|v| NAUTICAL_MILE.from_system_base.unwrap()(NAUTICAL_MILE.from_system_base.unwrap()(v))),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((2025.373 * 2025.373, 1.0)),
 };
 
 pub(crate) fn get_all() -> Vec<&'static Unit> {
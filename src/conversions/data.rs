@@ -0,0 +1,151 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use crate::conversions::{Dimension, System, Unit};
+
+// Data unit constants
+// The base unit is the byte. Decimal prefixes are powers of 1000, binary prefixes powers of 1024.
+
+pub(crate) static BITS_PER_BYTE: f64 = 8.0;
+static KIBI: f64 = 1024.0;
+
+pub static BIT: Unit = Unit {
+    name: "Bit",
+    symbol: "bit",
+    dimension: Dimension::Data,
+    system: System::Metric,
+    to_base: Some(|v| v / BITS_PER_BYTE),
+    from_base: Some(|v| v * BITS_PER_BYTE),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static BYTE: Unit = Unit {
+    name: "Byte",
+    symbol: "B",
+    dimension: Dimension::Data,
+    system: System::Metric,
+    to_base: None,
+    from_base: None,
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static KILOBYTE: Unit = Unit {
+    name: "Kilobyte",
+    symbol: "kB",
+    dimension: Dimension::Data,
+    system: System::Metric,
+    to_base: Some(Unit::from_kilo),
+    from_base: Some(Unit::to_kilo),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MEGABYTE: Unit = Unit {
+    name: "Megabyte",
+    symbol: "MB",
+    dimension: Dimension::Data,
+    system: System::Metric,
+    to_base: Some(Unit::from_mega),
+    from_base: Some(Unit::to_mega),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static GIGABYTE: Unit = Unit {
+    name: "Gigabyte",
+    symbol: "GB",
+    dimension: Dimension::Data,
+    system: System::Metric,
+    to_base: Some(Unit::from_giga),
+    from_base: Some(Unit::to_giga),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static TERABYTE: Unit = Unit {
+    name: "Terabyte",
+    symbol: "TB",
+    dimension: Dimension::Data,
+    system: System::Metric,
+    to_base: Some(|v| v * 1e12),
+    from_base: Some(|v| v / 1e12),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static KIBIBYTE: Unit = Unit {
+    name: "Kibibyte",
+    symbol: "KiB",
+    dimension: Dimension::Data,
+    system: System::Metric,
+    to_base: Some(|v| v * KIBI),
+    from_base: Some(|v| v / KIBI),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MEBIBYTE: Unit = Unit {
+    name: "Mebibyte",
+    symbol: "MiB",
+    dimension: Dimension::Data,
+    system: System::Metric,
+    to_base: Some(|v| v * KIBI * KIBI),
+    from_base: Some(|v| v / (KIBI * KIBI)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static GIBIBYTE: Unit = Unit {
+    name: "Gibibyte",
+    symbol: "GiB",
+    dimension: Dimension::Data,
+    system: System::Metric,
+    to_base: Some(|v| v * KIBI * KIBI * KIBI),
+    from_base: Some(|v| v / (KIBI * KIBI * KIBI)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+
+pub(crate) fn get_all() -> Vec<&'static Unit> {
+    vec![&BIT, &BYTE, &KILOBYTE, &MEGABYTE, &GIGABYTE, &TERABYTE,
+         &KIBIBYTE, &MEBIBYTE, &GIBIBYTE,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_near;
+    use crate::conversions::convert;
+    use crate::conversions::data::*;
+
+    #[test]
+    fn test_data() {
+        assert_near!(convert(&1.0, &BYTE, &BIT), 8.0);
+        assert_near!(convert(&1.0, &GIGABYTE, &MEGABYTE), 1000.0);
+        assert_near!(convert(&1.0, &MEBIBYTE, &KIBIBYTE), 1024.0);
+        assert_near!(convert(&1.0, &GIBIBYTE, &BYTE), 1073741824.0);
+        assert_near!(convert(&2.0, &TERABYTE, &GIGABYTE), 2000.0);
+    }
+}
@@ -0,0 +1,137 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use crate::conversions::{Dimension, System, Unit};
+use crate::conversions::data::BITS_PER_BYTE;
+
+// Data rate unit constants
+// The base unit is the bit per second, as network speeds are usually quoted in bits.
+
+pub static BIT_PER_SECOND: Unit = Unit {
+    name: "Bit per Second",
+    symbol: "bit/s",
+    dimension: Dimension::DataRate,
+    system: System::Metric,
+    to_base: None,
+    from_base: None,
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static KILOBIT_PER_SECOND: Unit = Unit {
+    name: "Kilobit per Second",
+    symbol: "kbit/s",
+    dimension: Dimension::DataRate,
+    system: System::Metric,
+    to_base: Some(Unit::from_kilo),
+    from_base: Some(Unit::to_kilo),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MEGABIT_PER_SECOND: Unit = Unit {
+    name: "Megabit per Second",
+    symbol: "Mbit/s",
+    dimension: Dimension::DataRate,
+    system: System::Metric,
+    to_base: Some(Unit::from_mega),
+    from_base: Some(Unit::to_mega),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static GIGABIT_PER_SECOND: Unit = Unit {
+    name: "Gigabit per Second",
+    symbol: "Gbit/s",
+    dimension: Dimension::DataRate,
+    system: System::Metric,
+    to_base: Some(Unit::from_giga),
+    from_base: Some(Unit::to_giga),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static BYTE_PER_SECOND: Unit = Unit {
+    name: "Byte per Second",
+    symbol: "B/s",
+    dimension: Dimension::DataRate,
+    system: System::Metric,
+    to_base: Some(|v| v * BITS_PER_BYTE),
+    from_base: Some(|v| v / BITS_PER_BYTE),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static KILOBYTE_PER_SECOND: Unit = Unit {
+    name: "Kilobyte per Second",
+    symbol: "kB/s",
+    dimension: Dimension::DataRate,
+    system: System::Metric,
+    to_base: Some(|v| v * BITS_PER_BYTE * 1e3),
+    from_base: Some(|v| v / (BITS_PER_BYTE * 1e3)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MEGABYTE_PER_SECOND: Unit = Unit {
+    name: "Megabyte per Second",
+    symbol: "MB/s",
+    dimension: Dimension::DataRate,
+    system: System::Metric,
+    to_base: Some(|v| v * BITS_PER_BYTE * 1e6),
+    from_base: Some(|v| v / (BITS_PER_BYTE * 1e6)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+
+pub(crate) fn get_all() -> Vec<&'static Unit> {
+    vec![&BIT_PER_SECOND, &KILOBIT_PER_SECOND, &MEGABIT_PER_SECOND, &GIGABIT_PER_SECOND,
+         &BYTE_PER_SECOND, &KILOBYTE_PER_SECOND, &MEGABYTE_PER_SECOND,
+    ]
+}
+
+/// The time in seconds to transfer a number of bytes at a rate in bits per second.
+pub(crate) fn download_time(bytes: f64, bits_per_second: f64) -> f64 {
+    bytes * BITS_PER_BYTE / bits_per_second
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_near;
+    use crate::conversions::convert;
+    use crate::conversions::data_rate::*;
+
+    #[test]
+    fn test_data_rate() {
+        assert_near!(convert(&1.0, &MEGABYTE_PER_SECOND, &MEGABIT_PER_SECOND), 8.0);
+        assert_near!(convert(&1.0, &GIGABIT_PER_SECOND, &KILOBIT_PER_SECOND), 1e6);
+        assert_near!(convert(&100.0, &KILOBYTE_PER_SECOND, &BYTE_PER_SECOND), 1e5);
+    }
+
+    #[test]
+    fn test_download_time() {
+        // 1 GB at 100 Mbit/s
+        assert_near!(download_time(1e9, 100e6), 80.0);
+        assert_near!(download_time(1.0, 8.0), 1.0);
+    }
+}
@@ -0,0 +1,98 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use crate::conversions::{Dimension, System, Unit};
+
+// Density unit constants
+pub static KILOGRAM_PER_CUBIC_METRE: Unit = Unit {
+    name: "Kilogram per Cubic Metre",
+    symbol: "kg/m³",
+    dimension: Dimension::Density,
+    system: System::Metric,
+    to_base: None,
+    from_base: None,
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static GRAM_PER_CUBIC_CENTIMETRE: Unit = Unit {
+    name: "Gram per Cubic Centimetre",
+    symbol: "g/cm³",
+    dimension: Dimension::Density,
+    system: System::Metric,
+    to_base: Some(|v| v * 1000.0),
+    from_base: Some(|v| v / 1000.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static GRAM_PER_MILLILITRE: Unit = Unit {
+    name: "Gram per Millilitre",
+    symbol: "g/mL",
+    dimension: Dimension::Density,
+    system: System::Metric,
+    to_base: Some(|v| v * 1000.0),
+    from_base: Some(|v| v / 1000.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+
+// Imperial units
+static KILOGRAMS_PER_CUBIC_METRE_PER_POUND_PER_CUBIC_FOOT: f64 = 16.01846337396;
+pub static POUND_PER_CUBIC_FOOT: Unit = Unit {
+    name: "Pound per Cubic Foot",
+    symbol: "lb/ft³",
+    dimension: Dimension::Density,
+    system: System::Imperial,
+    to_base: Some(|v| v * KILOGRAMS_PER_CUBIC_METRE_PER_POUND_PER_CUBIC_FOOT),
+    from_base: Some(|v| v / KILOGRAMS_PER_CUBIC_METRE_PER_POUND_PER_CUBIC_FOOT),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
+};
+
+pub(crate) fn get_all() -> Vec<&'static Unit> {
+    vec![&KILOGRAM_PER_CUBIC_METRE, &GRAM_PER_CUBIC_CENTIMETRE, &GRAM_PER_MILLILITRE,
+         &POUND_PER_CUBIC_FOOT,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_near;
+    use crate::conversions::convert;
+    use crate::conversions::density::*;
+
+    #[test]
+    fn test_metric_densities() {
+        assert_near!(convert(&1.0, &GRAM_PER_CUBIC_CENTIMETRE, &KILOGRAM_PER_CUBIC_METRE), 1000.0);
+        assert_near!(convert(&1.0, &GRAM_PER_MILLILITRE, &GRAM_PER_CUBIC_CENTIMETRE), 1.0);
+        assert_near!(convert(&7850.0, &KILOGRAM_PER_CUBIC_METRE, &GRAM_PER_CUBIC_CENTIMETRE), 7.85);
+    }
+
+    #[test]
+    fn test_imperial_densities() {
+        assert_near!(convert(&1.0, &POUND_PER_CUBIC_FOOT, &KILOGRAM_PER_CUBIC_METRE), 16.01846337396);
+        assert_near!(convert(&1.0, &GRAM_PER_CUBIC_CENTIMETRE, &POUND_PER_CUBIC_FOOT), 62.427960576);
+    }
+}
@@ -25,64 +25,123 @@ use crate::conversions::{Dimension, System, Unit};
 // Energy unit constants
 pub static JOULE: Unit = Unit {
     name: "Joule",
+    symbol: "J",
     dimension: Dimension::Energy,
     system: System::Metric,
     to_base: None,
     from_base: None,
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static KILOJOULE: Unit = Unit {
     name: "Kilojoule",
+    symbol: "kJ",
     dimension: Dimension::Energy,
     system: System::Metric,
     to_base: Some(Unit::from_kilo),
     from_base: Some(Unit::to_kilo),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static MEGAJOULE: Unit = Unit {
     name: "Megajoule",
+    symbol: "MJ",
     dimension: Dimension::Energy,
     system: System::Metric,
     to_base: Some(Unit::from_mega),
     from_base: Some(Unit::to_mega),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static GIGAJOULE: Unit = Unit {
     name: "Gigajoule",
+    symbol: "GJ",
     dimension: Dimension::Energy,
     system: System::Metric,
     to_base: Some(Unit::from_giga),
     from_base: Some(Unit::to_giga),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
+};
+pub static WATT_HOUR: Unit = Unit {
+    name: "Watt-hour",
+    symbol: "Wh",
+    dimension: Dimension::Energy,
+    system: System::Metric,
+    to_base: Some(|v| v * 3600.0),
+    from_base: Some(|v| v / 3600.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static KILOWATT_HOUR: Unit = Unit {
+    name: "Kilowatt-hour",
+    symbol: "kWh",
+    dimension: Dimension::Energy,
+    system: System::Metric,
+    to_base: Some(|v| v * 3.6e6),
+    from_base: Some(|v| v / 3.6e6),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static ELECTRONVOLT: Unit = Unit {
+    name: "Electronvolt",
+    symbol: "eV",
+    dimension: Dimension::Energy,
+    system: System::Metric,
+    to_base: Some(|v| v * 1.602176634e-19),
+    from_base: Some(|v| v / 1.602176634e-19),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
 };
 
-// Imperial units
+// Imperial units, which convert among themselves through the calorie
+static JOULES_PER_BTU: f64 = 1055.05585262;
+static JOULES_PER_CALORIE: f64 = 4.184;
 pub static BRITISH_THERMAL_UNIT: Unit = Unit {
     name: "BTU",
+    symbol: "BTU",
     dimension: Dimension::Energy,
     system: System::Imperial,
-    to_base: Some(|v| v * 1055.05585262),
-    from_base: Some(|v| v / 1055.05585262),
+    to_base: Some(|v| v * JOULES_PER_BTU),
+    from_base: Some(|v| v / JOULES_PER_BTU),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((JOULES_PER_BTU, JOULES_PER_CALORIE)),
 };
 pub static CALORIE: Unit = Unit {
     name: "Calorie",
+    symbol: "cal",
+    dimension: Dimension::Energy,
+    system: System::Imperial,
+    to_base: Some(|v| v * JOULES_PER_CALORIE),
+    from_base: Some(|v| v / JOULES_PER_CALORIE),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
+};
+pub static KILOCALORIE: Unit = Unit {
+    name: "Kilocalorie",
+    symbol: "kcal",
     dimension: Dimension::Energy,
     system: System::Imperial,
-    to_base: Some(|v| v * 4.184),
-    from_base: Some(|v| v / 4.184),
+    to_base: Some(|v| v * JOULES_PER_CALORIE * 1000.0),
+    from_base: Some(|v| v / (JOULES_PER_CALORIE * 1000.0)),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((1000.0, 1.0)),
 };
 
 pub(crate) fn get_all() -> Vec<&'static Unit> {
     vec![&JOULE, &KILOJOULE, &MEGAJOULE, &GIGAJOULE,
-         &BRITISH_THERMAL_UNIT, &CALORIE,
+         &WATT_HOUR, &KILOWATT_HOUR, &ELECTRONVOLT,
+         &BRITISH_THERMAL_UNIT, &CALORIE, &KILOCALORIE,
     ]
 }
 
@@ -90,19 +149,26 @@ pub(crate) fn get_all() -> Vec<&'static Unit> {
 mod tests {
     use crate::assert_near;
     use crate::conversions::convert;
-    use crate::conversions::energy::{BRITISH_THERMAL_UNIT, CALORIE, GIGAJOULE, JOULE, KILOJOULE, MEGAJOULE};
+    use crate::conversions::energy::{BRITISH_THERMAL_UNIT, CALORIE, ELECTRONVOLT, GIGAJOULE, JOULE, KILOCALORIE, KILOJOULE, KILOWATT_HOUR, MEGAJOULE, WATT_HOUR};
 
     #[test]
     fn test_metric_energy_units() {
         assert_near!(convert(&1.0, &KILOJOULE, &JOULE), 1000.0);
         assert_near!(convert(&1.0, &MEGAJOULE, &JOULE), 1_000_000.0);
         assert_near!(convert(&1.0, &GIGAJOULE, &JOULE), 1_000_000_000.0);
+        assert_near!(convert(&1.0, &KILOWATT_HOUR, &JOULE), 3.6e6);
+        assert_near!(convert(&1.0, &KILOWATT_HOUR, &WATT_HOUR), 1000.0);
+        assert_eq!(convert(&1.0, &ELECTRONVOLT, &JOULE), 1.602176634e-19);
     }
 
     #[test]
     fn test_imperial_energy_units() {
         assert_near!(convert(&1.0, &BRITISH_THERMAL_UNIT, &JOULE), 1055.05585262);
         assert_near!(convert(&1000.0, &CALORIE, &JOULE), 4184.0);
+        assert_near!(convert(&1.0, &KILOCALORIE, &JOULE), 4184.0);
+        assert_near!(convert(&1.0, &KILOCALORIE, &CALORIE), 1000.0);
+        assert_near!(convert(&1.0, &BRITISH_THERMAL_UNIT, &KILOCALORIE), 0.2521644);
+        assert_near!(convert(&1.0, &KILOCALORIE, &BRITISH_THERMAL_UNIT), 3.9656668);
     }
 
     #[test]
@@ -25,59 +25,71 @@ use crate::conversions::{Dimension, System, Unit};
 // Force unit constants
 pub static NEWTON: Unit = Unit {
     name: "Newton",
+    symbol: "N",
     dimension: Dimension::Force,
     system: System::Metric,
     to_base: None,
     from_base: None,
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static KILONEWTON: Unit = Unit {
     name: "Kilonewton",
+    symbol: "kN",
     dimension: Dimension::Force,
     system: System::Metric,
     to_base: Some(Unit::from_kilo),
     from_base: Some(Unit::to_kilo),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static MEGANEWTON: Unit = Unit {
     name: "Meganewton",
+    symbol: "MN",
     dimension: Dimension::Force,
     system: System::Metric,
     to_base: Some(Unit::from_mega),
     from_base: Some(Unit::to_mega),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static GIGANEWTON: Unit = Unit {
     name: "Giganewton",
+    symbol: "GN",
     dimension: Dimension::Force,
     system: System::Metric,
     to_base: Some(Unit::from_giga),
     from_base: Some(Unit::to_giga),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 
 // Imperial units
 pub static POUND_FORCE: Unit = Unit {
     name: "Pound",
+    symbol: "lbf",
     dimension: Dimension::Force,
     system: System::Imperial,
     to_base: Some(|v| v * 4.4482216152605),
     from_base: Some(|v| v / 4.4482216152605),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
 };
 pub static OUNCE_FORCE: Unit = Unit {
     name: "Ounce",
+    symbol: "ozf",
     dimension: Dimension::Force,
     system: System::Imperial,
     to_base: Some(|v| v * 0.278013851),
     from_base: Some(|v| v / 0.278013851),
-    to_system_base: Some(|v| v / 16.0),
-    from_system_base: Some(|v| v * 16.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 16.0)),
 };
 
 pub(crate) fn get_all() -> Vec<&'static Unit> {
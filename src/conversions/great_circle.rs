@@ -0,0 +1,65 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use crate::conversions::{convert, Unit};
+use crate::conversions::length::METRE;
+
+// The distance between two places on the earth isn't a unit conversion, it takes two latitudes
+// and two longitudes, but its result can be given in any length unit.
+
+/// The mean radius of the earth in metres
+static EARTH_RADIUS: f64 = 6_371_008.8;
+
+/// The great circle distance between two points given by latitude and longitude in degrees,
+/// in the units of `unit`. This uses the haversine formula, which treats the earth as a sphere,
+/// so it may be out by up to about 0.5%.
+pub(crate) fn great_circle_distance(lat1: f64, lon1: f64, lat2: f64, lon2: f64, unit: &Unit) -> Result<f64, String> {
+    if lat1.abs() > 90.0 || lat2.abs() > 90.0 {
+        return Err(String::from("Latitudes must be between -90 and 90 degrees"));
+    }
+    let (phi1, phi2) = (lat1.to_radians(), lat2.to_radians());
+    let d_phi = phi2 - phi1;
+    let d_lambda = (lon2 - lon1).to_radians();
+    let h = (d_phi / 2.0).sin().powi(2) + phi1.cos() * phi2.cos() * (d_lambda / 2.0).sin().powi(2);
+    // Rounding can take h just past 1 for points on opposite sides of the earth
+    let metres = 2.0 * EARTH_RADIUS * h.sqrt().min(1.0).asin();
+    Ok(convert(&metres, &METRE, unit))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_near;
+    use crate::conversions::great_circle::*;
+    use crate::conversions::length::{KILOMETRE, MILE};
+
+    #[test]
+    fn test_great_circle_distance() {
+        // London to Paris
+        assert_near!(great_circle_distance(51.5074, -0.1278, 48.8566, 2.3522, &KILOMETRE).unwrap(), 343.556, 0.001);
+        // New York to London
+        assert_near!(great_circle_distance(40.7128, -74.0060, 51.5074, -0.1278, &MILE).unwrap(), 3461.18, 0.01);
+        // Half way round the equator
+        assert_near!(great_circle_distance(0.0, 0.0, 0.0, 180.0, &KILOMETRE).unwrap(), 20015.114, 0.001);
+        assert_eq!(great_circle_distance(10.0, 20.0, 10.0, 20.0, &METRE).unwrap(), 0.0);
+        assert!(great_circle_distance(91.0, 0.0, 0.0, 0.0, &METRE).is_err());
+    }
+}
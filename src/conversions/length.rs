@@ -20,6 +20,8 @@
  *
  */
 
+use std::sync::atomic::{AtomicU64, Ordering};
+
 use crate::conversions::{Dimension, System, Unit};
 
 /// These are the units of the weight dimension.
@@ -28,68 +30,108 @@ use crate::conversions::{Dimension, System, Unit};
 
 pub static METRE: Unit = Unit {
     name: "Metre",
+    symbol: "m",
     dimension: Dimension::Length,
     system: System::Metric,
     to_base: None,
     from_base: None,
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static CENTIMETRE: Unit = Unit {
     name: "Centimetre",
+    symbol: "cm",
     dimension: Dimension::Length,
     system: System::Metric,
     to_base: Some(|v| v / 100.0),
     from_base: Some(|v| v * 100.0),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static MILLIMETRE: Unit = Unit {
     name: "Millimetre",
+    symbol: "mm",
     dimension: Dimension::Length,
     system: System::Metric,
     to_base: Some(Unit::from_milli),
     from_base: Some(Unit::to_milli),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static MICROMETRE: Unit = Unit {
     name: "Micrometre",
+    symbol: "µm",
     dimension: Dimension::Length,
     system: System::Metric,
     to_base: Some(Unit::from_micro),
     from_base: Some(Unit::to_micro),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
+};
+static METRES_PER_ANGSTROM: f64 = 1.0e-10;
+pub static ANGSTROM: Unit = Unit {
+    name: "Angstrom",
+    symbol: "Å",
+    dimension: Dimension::Length,
+    system: System::Metric,
+    to_base: Some(|v| v * METRES_PER_ANGSTROM),
+    from_base: Some(|v| v / METRES_PER_ANGSTROM),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
 };
 pub static KILOMETRE: Unit = Unit {
     name: "Kilometre",
+    symbol: "km",
     dimension: Dimension::Length,
     system: System::Metric,
     to_base: Some(Unit::from_kilo),
     from_base: Some(Unit::to_kilo),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
+};
+// Astronomical lengths are defined exactly in metres and are applied as a single multiplication or
+// division, so a round trip is good to about one part in 10^15, the precision of an f64.
+static METRES_PER_ASTRONOMICAL_UNIT: f64 = 149597870700.0;
+pub static ASTRONOMICAL_UNIT: Unit = Unit {
+    name: "Astronomical unit",
+    symbol: "au",
+    dimension: Dimension::Length,
+    system: System::Metric,
+    to_base: Some(|v| v * METRES_PER_ASTRONOMICAL_UNIT),
+    from_base: Some(|v| v / METRES_PER_ASTRONOMICAL_UNIT),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
 };
 static METRES_PER_LIGHTYEAR: f64 = 9460730472580800.0;
 pub static LIGHTYEAR: Unit = Unit {
     name: "Lightyear",
+    symbol: "ly",
     dimension: Dimension::Length,
     system: System::Metric,
     to_base: Some(|v| v * METRES_PER_LIGHTYEAR),
     from_base: Some(|v| v / METRES_PER_LIGHTYEAR),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 static METRES_PER_PARSEC: f64 = 30856775814913670.0;
 pub static PARSEC: Unit = Unit {
     name: "Parsec",
+    symbol: "pc",
     dimension: Dimension::Length,
     system: System::Metric,
     to_base: Some(|v| v * METRES_PER_PARSEC),
     from_base: Some(|v| v / METRES_PER_PARSEC),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 
 // Imperial units
@@ -97,54 +139,194 @@ pub static PARSEC: Unit = Unit {
 static YARDS_PER_METRE: f64 = 1.093613;
 pub static YARD: Unit = Unit {
     name: "Yard",
+    symbol: "yd",
     dimension: Dimension::Length,
     system: System::Imperial,
     to_base: Some(|v| v / YARDS_PER_METRE),
     from_base: Some(|v| v * YARDS_PER_METRE),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
 };
 pub static FOOT: Unit = Unit {
     name: "Foot",
+    symbol: "ft",
     dimension: Dimension::Length,
     system: System::Imperial,
     to_base: Some(|v| v / (YARDS_PER_METRE * 3.0)),
     from_base: Some(|v| v * (YARDS_PER_METRE * 3.0)),
-    to_system_base: Some(|v| v / 3.0),
-    from_system_base: Some(|v| v * 3.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 3.0)),
 };
 pub static INCH: Unit = Unit {
     name: "Inch",
+    symbol: "in",
     dimension: Dimension::Length,
     system: System::Imperial,
     to_base: Some(|v| v / (YARDS_PER_METRE * 36.0)),
     from_base: Some(|v| v * (YARDS_PER_METRE * 36.0)),
-    to_system_base: Some(|v| v / 36.0),
-    from_system_base: Some(|v| v * 36.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 36.0)),
 };
 pub static MILE: Unit = Unit {
     name: "Mile",
+    symbol: "mi",
     dimension: Dimension::Length,
     system: System::Imperial,
     to_base: Some(|v| v / (YARDS_PER_METRE / 1760.0)),
     from_base: Some(|v| v * (YARDS_PER_METRE / 1760.0)),
-    to_system_base: Some(|v| v * 1760.0),
-    from_system_base: Some(|v| v / 1760.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1760.0, 1.0)),
 };
 pub static NAUTICAL_MILE: Unit = Unit {
     name: "Nm",
+    symbol: "nmi",
     dimension: Dimension::Length,
     system: System::Imperial,
     to_base: Some(|v| v / (YARDS_PER_METRE / 2025.373)),
     from_base: Some(|v| v * (YARDS_PER_METRE / 2025.373)),
-    to_system_base: Some(|v| v * 2025.373),
-    from_system_base: Some(|v| v / 2025.373),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((2025.373, 1.0)),
+};
+
+// Nautical and surveying units, also based on the yard
+pub static FATHOM: Unit = Unit {
+    name: "Fathom",
+    symbol: "ftm",
+    dimension: Dimension::Length,
+    system: System::Imperial,
+    to_base: Some(|v| v / (YARDS_PER_METRE / 2.0)),
+    from_base: Some(|v| v * (YARDS_PER_METRE / 2.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((2.0, 1.0)),
+};
+pub static ROD: Unit = Unit {
+    name: "Rod",
+    symbol: "rd",
+    dimension: Dimension::Length,
+    system: System::Imperial,
+    to_base: Some(|v| v / (YARDS_PER_METRE / 5.5)),
+    from_base: Some(|v| v * (YARDS_PER_METRE / 5.5)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((11.0, 2.0)),
+};
+pub static CHAIN: Unit = Unit {
+    name: "Chain",
+    symbol: "ch",
+    dimension: Dimension::Length,
+    system: System::Imperial,
+    to_base: Some(|v| v / (YARDS_PER_METRE / 22.0)),
+    from_base: Some(|v| v * (YARDS_PER_METRE / 22.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((22.0, 1.0)),
+};
+pub static FURLONG: Unit = Unit {
+    name: "Furlong",
+    symbol: "fur",
+    dimension: Dimension::Length,
+    system: System::Imperial,
+    to_base: Some(|v| v / (YARDS_PER_METRE / 220.0)),
+    from_base: Some(|v| v * (YARDS_PER_METRE / 220.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((220.0, 1.0)),
+};
+pub static LEAGUE: Unit = Unit {
+    name: "League",
+    symbol: "lea",
+    dimension: Dimension::Length,
+    system: System::Imperial,
+    to_base: Some(|v| v / (YARDS_PER_METRE / 5280.0)),
+    from_base: Some(|v| v * (YARDS_PER_METRE / 5280.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((5280.0, 1.0)),
+};
+
+// The US survey foot is defined as exactly 1200/3937 metres, a little longer than the foot
+pub static US_SURVEY_FOOT: Unit = Unit {
+    name: "US Survey Foot",
+    symbol: "US ft",
+    dimension: Dimension::Length,
+    system: System::US,
+    to_base: Some(|v| v * 1200.0 / 3937.0),
+    from_base: Some(|v| v * 3937.0 / 1200.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
+};
+
+
+// Typographic units, based on the inch
+
+pub static POINT: Unit = Unit {
+    name: "Point",
+    symbol: "pt",
+    dimension: Dimension::Length,
+    system: System::Imperial,
+    to_base: Some(|v| v / (YARDS_PER_METRE * 36.0 * 72.0)),
+    from_base: Some(|v| v * (YARDS_PER_METRE * 36.0 * 72.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 36.0 * 72.0)),
 };
+pub static PICA: Unit = Unit {
+    name: "Pica",
+    symbol: "pica",
+    dimension: Dimension::Length,
+    system: System::Imperial,
+    to_base: Some(|v| v / (YARDS_PER_METRE * 36.0 * 6.0)),
+    from_base: Some(|v| v * (YARDS_PER_METRE * 36.0 * 6.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 36.0 * 6.0)),
+};
+/// The size of a pixel depends on the display, so it is set by `set_dpi`.
+pub static PIXEL: Unit = Unit {
+    name: "Pixel",
+    symbol: "px",
+    dimension: Dimension::Length,
+    system: System::Imperial,
+    to_base: Some(|v| v / (YARDS_PER_METRE * 36.0 * dpi())),
+    from_base: Some(|v| v * (YARDS_PER_METRE * 36.0 * dpi())),
+    to_system_base: Some(|v| v / (36.0 * dpi())),
+    from_system_base: Some(|v| v * (36.0 * dpi())),
+    system_factor: None,
+};
+
+pub(crate) static DEFAULT_DPI: f64 = 96.0;
+/// The bits of the pixels per inch, zero until it is set
+static PIXELS_PER_INCH: AtomicU64 = AtomicU64::new(0);
+
+/// The pixels per inch used to convert pixels.
+pub(crate) fn dpi() -> f64 {
+    pixels_per_inch(f64::from_bits(PIXELS_PER_INCH.load(Ordering::Relaxed)))
+}
+
+/// The pixels per inch for a DPI setting, the default if it isn't positive.
+fn pixels_per_inch(dpi: f64) -> f64 {
+    if dpi > 0.0 { dpi } else { DEFAULT_DPI }
+}
+
+/// Set the pixels per inch of the display, e.g. from the DPI preference. Anything that isn't
+/// positive gives the default.
+pub(crate) fn set_dpi(dpi: f64) {
+    PIXELS_PER_INCH.store(dpi.to_bits(), Ordering::Relaxed);
+}
 
 pub(crate) fn get_all() -> Vec<&'static Unit> {
-    vec![&METRE, &CENTIMETRE, &MILLIMETRE, &MICROMETRE,
-         &KILOMETRE, &LIGHTYEAR, &PARSEC, &YARD,
-         &FOOT, &INCH, &MILE, &NAUTICAL_MILE
+    vec![&METRE, &CENTIMETRE, &MILLIMETRE, &MICROMETRE, &ANGSTROM,
+         &KILOMETRE, &ASTRONOMICAL_UNIT, &LIGHTYEAR, &PARSEC, &YARD,
+         &FOOT, &INCH, &MILE, &NAUTICAL_MILE,
+         &FATHOM, &ROD, &CHAIN, &FURLONG, &LEAGUE, &US_SURVEY_FOOT,
+         &POINT, &PICA, &PIXEL
     ]
 }
 
@@ -195,4 +377,63 @@ mod tests {
         assert_near!(convert(&23.66, &METRE, &NAUTICAL_MILE), 0.01277537797);
 
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_nautical_and_survey_lengths() {
+        assert_near!(convert(&1.0, &FURLONG, &METRE), 201.168, 1e-3);
+        assert_near!(convert(&1.0, &FATHOM, &METRE), 1.8288, 1e-5);
+        assert_eq!(convert(&1.0, &FATHOM, &FOOT), 6.0);
+        assert_eq!(convert(&1.0, &CHAIN, &ROD), 4.0);
+        assert_eq!(convert(&10.0, &CHAIN, &FURLONG), 1.0);
+        assert_eq!(convert(&8.0, &FURLONG, &MILE), 1.0);
+        assert_eq!(convert(&1.0, &LEAGUE, &MILE), 3.0);
+        assert_near!(convert(&1.0, &US_SURVEY_FOOT, &METRE), 0.3048006096);
+        assert_near!(convert(&3937.0, &US_SURVEY_FOOT, &METRE), 1200.0);
+    }
+
+    #[test]
+    fn test_astronomical_round_trips() {
+        for v in [1.0, 23.66, 0.001, 4.2465, 1.0e6] {
+            for unit in [&ASTRONOMICAL_UNIT, &LIGHTYEAR, &PARSEC] {
+                for via in [&METRE, &KILOMETRE, &MILE] {
+                    let back = convert(&convert(&v, unit, via), via, unit);
+                    assert!(((back - v) / v).abs() < 1e-15, "{} {} via {} gave {}", v, unit.name, via.name, back);
+                }
+            }
+        }
+        assert_eq!(convert(&1.0, &LIGHTYEAR, &METRE), 9460730472580800.0);
+        assert_near!(convert(&1.0, &PARSEC, &LIGHTYEAR), 3.2615638, 1e-7);
+        assert_eq!(convert(&1.0, &ASTRONOMICAL_UNIT, &METRE), 1.495978707e11);
+    }
+
+    #[test]
+    fn test_angstrom() {
+        assert_eq!(convert(&1.0, &ANGSTROM, &METRE), 1e-10);
+        assert_near!(convert(&5500.0, &ANGSTROM, &MICROMETRE), 0.55);
+    }
+
+    #[test]
+    fn test_typography() {
+        assert_eq!(convert(&72.0, &POINT, &INCH), 1.0);
+        assert_eq!(convert(&12.0, &POINT, &PICA), 1.0);
+        assert_eq!(convert(&6.0, &PICA, &INCH), 1.0);
+        assert_near!(convert(&1.0, &POINT, &MILLIMETRE), 0.3527778);
+    }
+
+    #[test]
+    fn test_pixels_per_inch() {
+        assert_eq!(pixels_per_inch(144.0), 144.0);
+        assert_eq!(pixels_per_inch(0.0), DEFAULT_DPI);
+        assert_eq!(pixels_per_inch(-72.0), DEFAULT_DPI);
+        assert_eq!(pixels_per_inch(f64::NAN), DEFAULT_DPI);
+    }
+
+    #[test]
+    fn test_pixels() {
+        // The DPI is only set by the UI, so tests see the default
+        assert_eq!(convert(&96.0, &PIXEL, &INCH), 1.0);
+        assert_eq!(convert(&2.0, &INCH, &PIXEL), 192.0);
+        assert_near!(convert(&4.0, &PIXEL, &POINT), 3.0);
+        assert_near!(convert(&1.0, &PIXEL, &MILLIMETRE), 25.4 / 96.0);
+    }
+}
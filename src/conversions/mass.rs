@@ -28,48 +28,82 @@ use crate::conversions::{Dimension, System, Unit};
 
 pub static KILOGRAM: Unit = Unit {
     name: "Kilogram",
+    symbol: "kg",
     dimension: Dimension::Mass,
     system: System::Metric,
     to_base: None,
     from_base: None,
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static GRAM: Unit = Unit {
     name: "Gram",
+    symbol: "g",
     dimension: Dimension::Mass,
     system: System::Metric,
     to_base: Some(Unit::to_kilo),
     from_base: Some(Unit::from_kilo),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static MILLIGRAM: Unit = Unit {
     name: "Milligram",
+    symbol: "mg",
     dimension: Dimension::Mass,
     system: System::Metric,
     to_base: Some(|v| GRAM.to_base.unwrap()(Unit::from_milli(v))),
     from_base: Some(|v| Unit::to_milli(GRAM.from_base.unwrap()(v))),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static MICROGRAM: Unit = Unit {
     name: "Microgram",
+    symbol: "µg",
     dimension: Dimension::Mass,
     system: System::Metric,
     to_base: Some(|v| GRAM.to_base.unwrap()(Unit::from_micro(v))),
     from_base: Some(|v| Unit::to_micro(GRAM.from_base.unwrap()(v))),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static TONNE: Unit = Unit {
     name: "Tonne",
+    symbol: "t",
     dimension: Dimension::Mass,
     system: System::Metric,
     to_base: Some(Unit::from_kilo),
     from_base: Some(Unit::to_kilo),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
+};
+static KILOS_PER_CARAT: f64 = 0.0002;
+pub static CARAT: Unit = Unit {
+    name: "Carat",
+    symbol: "ct",
+    dimension: Dimension::Mass,
+    system: System::Metric,
+    to_base: Some(|v| v * KILOS_PER_CARAT),
+    from_base: Some(|v| v / KILOS_PER_CARAT),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+static KILOS_PER_ATOMIC_MASS_UNIT: f64 = 1.66053906660e-27;
+pub static ATOMIC_MASS_UNIT: Unit = Unit {
+    name: "Atomic mass unit",
+    symbol: "u",
+    dimension: Dimension::Mass,
+    system: System::Metric,
+    to_base: Some(|v| v * KILOS_PER_ATOMIC_MASS_UNIT),
+    from_base: Some(|v| v / KILOS_PER_ATOMIC_MASS_UNIT),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
 };
 
 // Imperial
@@ -77,44 +111,111 @@ pub static TONNE: Unit = Unit {
 pub static OUNCES_PER_KILO: f64 = 35.2739619495804;
 pub static OUNCE: Unit = Unit {
     name: "Ounce",
+    symbol: "oz",
     dimension: Dimension::Mass,
     system: System::Imperial,
     to_base: Some(|v| v / OUNCES_PER_KILO),
     from_base: Some(|v| v * OUNCES_PER_KILO),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
 };
 pub static POUND: Unit = Unit {
     name: "Pound",
+    symbol: "lb",
     dimension: Dimension::Mass,
     system: System::Imperial,
     to_base: Some( |v| v * 16.0 / OUNCES_PER_KILO),
     from_base: Some( |v| v * OUNCES_PER_KILO / 16.0),
-    to_system_base: Some(|v| v * 16.0),
-    from_system_base: Some(|v| v / 16.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((16.0, 1.0)),
+};
+// Troy weights are used for precious metals. They, and the avoirdupois ounce, are whole numbers
+// of grains, so they are converted through the ounce as a number of grains.
+static GRAINS_PER_OUNCE: f64 = 437.5;
+pub static GRAIN: Unit = Unit {
+    name: "Grain",
+    symbol: "gr",
+    dimension: Dimension::Mass,
+    system: System::Imperial,
+    to_base: Some(|v| v / GRAINS_PER_OUNCE / OUNCES_PER_KILO),
+    from_base: Some(|v| v * OUNCES_PER_KILO * GRAINS_PER_OUNCE),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, GRAINS_PER_OUNCE)),
+};
+pub static PENNYWEIGHT: Unit = Unit {
+    name: "Pennyweight",
+    symbol: "dwt",
+    dimension: Dimension::Mass,
+    system: System::Imperial,
+    to_base: Some(|v| v * 24.0 / GRAINS_PER_OUNCE / OUNCES_PER_KILO),
+    from_base: Some(|v| v * OUNCES_PER_KILO / (24.0 / GRAINS_PER_OUNCE)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((24.0, GRAINS_PER_OUNCE)),
+};
+pub static TROY_OUNCE: Unit = Unit {
+    name: "Troy Ounce",
+    symbol: "oz t",
+    dimension: Dimension::Mass,
+    system: System::Imperial,
+    to_base: Some(|v| v * 480.0 / GRAINS_PER_OUNCE / OUNCES_PER_KILO),
+    from_base: Some(|v| v * OUNCES_PER_KILO / (480.0 / GRAINS_PER_OUNCE)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((480.0, GRAINS_PER_OUNCE)),
+};
+pub static TROY_POUND: Unit = Unit {
+    name: "Troy Pound",
+    symbol: "lb t",
+    dimension: Dimension::Mass,
+    system: System::Imperial,
+    to_base: Some(|v| v * 5760.0 / GRAINS_PER_OUNCE / OUNCES_PER_KILO),
+    from_base: Some(|v| v * OUNCES_PER_KILO / (5760.0 / GRAINS_PER_OUNCE)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((5760.0, GRAINS_PER_OUNCE)),
+};
+pub static STONE: Unit = Unit {
+    name: "Stone",
+    symbol: "st",
+    dimension: Dimension::Mass,
+    system: System::Imperial,
+    to_base: Some(|v| v * 14.0 * 16.0 / OUNCES_PER_KILO),
+    from_base: Some(|v| v * OUNCES_PER_KILO / (14.0 * 16.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((14.0 * 16.0, 1.0)),
 };
 pub static TON: Unit = Unit {
     name: "Long Ton",
+    symbol: "long ton",
     dimension: Dimension::Mass,
     system: System::Imperial,
     to_base: Some(|v| v * 2240.0 * 16.0 / OUNCES_PER_KILO),
     from_base: Some(|v| v * OUNCES_PER_KILO / (2240.0 * 16.0)),
-    to_system_base: Some(|v| v * 2240.0 * 16.0),
-    from_system_base: Some(|v| v / (2240.0 * 16.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((2240.0 * 16.0, 1.0)),
 };
 pub static TON_SHORT: Unit = Unit {
     name: "Short Ton",
+    symbol: "short ton",
     dimension: Dimension::Mass,
     system: System::Imperial,
     to_base: Some(|v| v * 2000.0 * 16.0 / OUNCES_PER_KILO),
     from_base: Some(|v| v * OUNCES_PER_KILO / (2000.0 * 16.0)),
-    to_system_base: Some(|v| v * 2000.0 * 16.0),
-    from_system_base: Some(|v| v / (2000.0 * 16.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((2000.0 * 16.0, 1.0)),
 };
 
 pub(crate) fn get_all() -> Vec<&'static Unit> {
-    vec![&KILOGRAM, &GRAM, &MILLIGRAM, &MICROGRAM, &TONNE,
-         &OUNCE, &POUND, &TON, &TON_SHORT
+    vec![&KILOGRAM, &GRAM, &MILLIGRAM, &MICROGRAM, &TONNE, &CARAT, &ATOMIC_MASS_UNIT,
+         &GRAIN, &PENNYWEIGHT, &TROY_OUNCE, &TROY_POUND,
+         &OUNCE, &POUND, &STONE, &TON, &TON_SHORT
     ]
 }
 
@@ -137,6 +238,16 @@ mod tests {
         assert_eq!(convert(&23.66, &MILLIGRAM, &MICROGRAM), 23660.0);
     }
     #[test]
+    fn test_carat_and_atomic_mass_unit() {
+        assert_near!(convert(&1.0, &CARAT, &KILOGRAM), 0.0002);
+        assert_near!(convert(&5.0, &CARAT, &GRAM), 1.0);
+        assert_eq!(convert(&1.0, &ATOMIC_MASS_UNIT, &KILOGRAM), 1.66053906660e-27);
+        for v in [1.0, 12.0, 6.02214076e23] {
+            let back = convert(&convert(&v, &ATOMIC_MASS_UNIT, &KILOGRAM), &KILOGRAM, &ATOMIC_MASS_UNIT);
+            assert!(((back - v) / v).abs() < 1e-15, "{} u gave {}", v, back);
+        }
+    }
+    #[test]
     fn test_all_imperial_weights() {
         assert_eq!(convert(&23.66, &OUNCE, &POUND), 23.66 / 16.0);
         assert_eq!(convert(&23.66, &POUND, &OUNCE), 23.66 * 16.0);
@@ -157,4 +268,14 @@ mod tests {
         assert_near!(convert(&23.66, &KILOGRAM, &TON_SHORT), 23.66 * OUNCES_PER_KILO / (2000.0 * 16.0));
 
     }
+    #[test]
+    fn test_troy_and_stone() {
+        assert_near!(convert(&1.0, &STONE, &POUND), 14.0);
+        assert_near!(convert(&1.0, &TROY_OUNCE, &GRAM), 31.1035, 0.0001);
+        assert_near!(convert(&480.0, &GRAIN, &TROY_OUNCE), 1.0);
+        assert_near!(convert(&20.0, &PENNYWEIGHT, &TROY_OUNCE), 1.0);
+        assert_near!(convert(&1.0, &TROY_POUND, &TROY_OUNCE), 12.0);
+        assert_near!(convert(&7000.0, &GRAIN, &POUND), 1.0);
+        assert_near!(convert(&1.0, &GRAIN, &MILLIGRAM), 64.79891, 0.00001);
+    }
 }
@@ -25,19 +25,31 @@ use std::cmp::PartialEq;
 use std::fmt::{Display, Formatter};
 
 use log::warn;
+use strum::IntoEnumIterator;
 use strum_macros::{Display, EnumIter};
 
 use crate::conversions::System::Metric;
 
-mod mass;
-mod length;
-mod area;
-mod temperature;
-mod volume;
-mod power;
-mod torque;
-mod force;
-mod energy;
+pub(crate) mod mass;
+pub(crate) mod length;
+pub(crate) mod area;
+pub(crate) mod temperature;
+pub(crate) mod volume;
+pub(crate) mod power;
+pub(crate) mod torque;
+pub(crate) mod force;
+pub(crate) mod energy;
+pub(crate) mod time;
+pub(crate) mod density;
+pub(crate) mod data;
+pub(crate) mod data_rate;
+pub(crate) mod current;
+pub(crate) mod voltage;
+pub(crate) mod resistance;
+pub(crate) mod angle;
+pub(crate) mod wavelength;
+pub(crate) mod great_circle;
+pub(crate) mod slope;
 
 /// There are multiple measurement systems.
 /// You can convert both within and between measurement systems.
@@ -50,7 +62,7 @@ pub(crate) enum System {
     #[default]
     Metric,
     Imperial,
-    US, // Only used for Volume
+    US, // Only used for Volume and the survey foot
 }
 
 impl System {
@@ -71,7 +83,16 @@ pub(crate) enum Dimension {
     Power,
     Torque,
     Force,
-    Energy
+    Energy,
+    Time,
+    Density,
+    Data,
+    #[strum(to_string = "Data Rate")]
+    DataRate,
+    Current,
+    Voltage,
+    Resistance,
+    Angle,
 }
 
 #[derive(Debug, Default, PartialEq, Clone, Copy)]
@@ -86,6 +107,8 @@ pub(crate) enum ConversionDirection {
 #[derive(Debug, Default, Clone)]
 pub struct Unit {
     pub(crate) name: &'static str,
+    /// The abbreviation for the unit, used to enter quantities in expressions
+    pub(crate) symbol: &'static str,
     /// The dimension the unit measures
     dimension: Dimension,
     /// The system the unit belongs to
@@ -95,13 +118,40 @@ pub struct Unit {
     to_base: Option<fn(f64) -> f64>,
     /// Function that converts this unit from the base unit
     from_base: Option<fn(f64) -> f64>,
-    /// Function that converts this unit to the system base unit
+    /// Function that converts this unit to the system base unit, only for units that aren't a
+    /// fixed multiple of it, e.g. temperatures have an offset and pixels depend on the display.
     /// All Units that share a system and dimension must convert to the same system base.
     to_system_base: Option<fn(f64) -> f64>,
-    /// Function that converts this unit from the system base unit
+    /// Function that converts this unit from the system base unit, as for `to_system_base`
     from_system_base: Option<fn(f64) -> f64>,
+    /// How many system base units this unit is, as a fraction (numerator, denominator), so whole
+    /// number factors like 12 inches = 1 foot stay exact. None if converting to the system base
+    /// isn't a simple factor, in which case the system base functions are used.
+    system_factor: Option<(f64, f64)>,
 }
 impl Unit {
+    pub(crate) fn dimension(&self) -> &Dimension {
+        &self.dimension
+    }
+
+    /// Convert a value in this unit to the system base unit.
+    fn to_system(&self, v: f64) -> f64 {
+        match (self.system_factor, self.to_system_base) {
+            (Some((n, d)), _) => v * n / d,
+            (None, Some(f)) => f(v),
+            (None, None) => v,
+        }
+    }
+
+    /// Convert a value in the system base unit to this unit.
+    fn from_system(&self, v: f64) -> f64 {
+        match (self.system_factor, self.from_system_base) {
+            (Some((n, d)), _) => v * d / n,
+            (None, Some(f)) => f(v),
+            (None, None) => v,
+        }
+    }
+
     /// Convenience functions to convert to milli, micro, kilo, mega etc
     fn to_milli(v: f64) -> f64 {
         v * 1e3
@@ -152,32 +202,54 @@ impl PartialEq for Unit {
     }
 }
 
-pub(crate) fn try_convert(value: &f64, from: &Option<&Unit>, to: &Option<&Unit>) -> f64 {
+/// Convert a value if both units are set. If `figures` is given the value is first rounded to
+/// that many significant figures, so that the result doesn't claim more precision than the input.
+pub(crate) fn try_convert(value: &f64, from: &Option<&Unit>, to: &Option<&Unit>, figures: Option<usize>) -> f64 {
     if let (Some(from), Some(to)) = (from, to) {
-        convert(value, from, to)
+        let value = figures.map_or(*value, |figures| round_significant(*value, figures));
+        convert(&value, from, to)
     } else {
         // Doing a conversion with no unit set
         warn!("Doing a conversion, but units not set");
         0.0
     }
 }
+/// Round a value to a number of significant figures, e.g. 1234.5 to 3 figures is 1230.
+pub(crate) fn round_significant(value: f64, figures: usize) -> f64 {
+    if value == 0.0 || !value.is_finite() || figures == 0 {
+        return value;
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    let shift = figures as i32 - 1 - magnitude;
+    // Scale by whole powers of ten, as dividing by a fraction like 0.1 isn't exact
+    if shift >= 0 {
+        let factor = 10f64.powi(shift);
+        (value * factor).round() / factor
+    } else {
+        let factor = 10f64.powi(-shift);
+        (value / factor).round() * factor
+    }
+}
+
 pub(crate) fn convert(value: &f64, from: &Unit, to: &Unit) -> f64 {
         if from == to {
             return value.clone()
         }
 
-        // Let's get the functions we need to convert the value to and from the base unit
-        // Funtions may be None if the unit is the base
-        let (to_base, from_base) =
-
-            // We need to see if these are of the same system and have a system base unit.
-            if from.system == to.system && from.dimension == to.dimension && !from.system.is_default() {
-                (&from.to_system_base, &to.from_system_base)
-            } else {
-                (&from.to_base, &to.from_base)
+        // We need to see if these are of the same system and have a system base unit.
+        if from.system == to.system && from.dimension == to.dimension && !from.system.is_default() {
+            // Applying the two factors one after the other accumulates rounding error, so they
+            // are combined into one
+            if let (Some((n1, d1)), Some((n2, d2))) = (from.system_factor, to.system_factor) {
+                let (n, d) = reduce(n1 * d2, d1 * n2);
+                return value * n / d;
             }
-            ;
+            return to.from_system(from.to_system(*value));
+        }
 
+        // Let's get the functions we need to convert the value to and from the base unit
+        // Funtions may be None if the unit is the base
+        let (to_base, from_base) = (&from.to_base, &to.from_base);
         let mut result = *value;
         if let Some(f) = to_base {
             result = f(result);
@@ -188,6 +260,22 @@ pub(crate) fn convert(value: &f64, from: &Unit, to: &Unit) -> f64 {
         result
 }
 
+/// Reduce a fraction of whole numbers to its lowest terms.
+fn reduce(n: f64, d: f64) -> (f64, f64) {
+    if n.fract() != 0.0 || d.fract() != 0.0 || n.abs() > 2f64.powi(53) || d.abs() > 2f64.powi(53) {
+        return (n, d);
+    }
+    let (mut a, mut b) = (n.abs() as u64, d.abs() as u64);
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    if a <= 1 {
+        (n, d)
+    } else {
+        (n / a as f64, d / a as f64)
+    }
+}
+
 pub(crate) fn get_units(dimension: &Dimension) -> Vec<&'static Unit> {
     match dimension {
         Dimension::Length => {
@@ -217,13 +305,112 @@ pub(crate) fn get_units(dimension: &Dimension) -> Vec<&'static Unit> {
         Dimension::Energy => {
             energy::get_all()
         }
+        Dimension::Time => {
+            time::get_all()
+        }
+        Dimension::Density => {
+            density::get_all()
+        }
+        Dimension::Data => {
+            data::get_all()
+        }
+        Dimension::DataRate => {
+            data_rate::get_all()
+        }
+        Dimension::Current => {
+            current::get_all()
+        }
+        Dimension::Voltage => {
+            voltage::get_all()
+        }
+        Dimension::Resistance => {
+            resistance::get_all()
+        }
+        Dimension::Angle => {
+            angle::get_all()
+        }
     }
 }
 
+/// The units a unit can be converted to. Only those of a different system are given if
+/// `other_systems_only`, unless all the units of the dimension are in the same system.
+pub(crate) fn get_to_units(from: &Unit, other_systems_only: bool) -> Vec<&'static Unit> {
+    let units = get_units(from.dimension());
+    if !other_systems_only {
+        return units;
+    }
+    let others: Vec<&'static Unit> = units.iter()
+        .filter(|u| u.system != from.system)
+        .copied()
+        .collect();
+    if others.is_empty() { units } else { others }
+}
+
+/// All units across all dimensions.
+pub(crate) fn get_all_units() -> Vec<&'static Unit> {
+    Dimension::iter()
+        .flat_map(|d| get_units(&d))
+        .collect()
+}
+
+/// Find a unit by name, searching all dimensions.
+pub(crate) fn find_unit(name: &str) -> Option<&'static Unit> {
+    Dimension::iter()
+        .flat_map(|d| get_units(&d))
+        .find(|u| u.name == name)
+}
+
+/// Find a unit by its symbol, e.g. `km`.
+pub(crate) fn find_unit_by_symbol(symbol: &str) -> Option<&'static Unit> {
+    get_all_units().into_iter()
+        .find(|u| u.symbol == symbol)
+}
+
+/// Describe the available units for the command line. With no dimension the dimensions are listed,
+/// otherwise the name, symbol and system of each unit in the dimension.
+pub(crate) fn list_units(dimension: Option<&str>) -> Result<String, String> {
+    match dimension {
+        None => Ok(Dimension::iter()
+            .map(|d| format!("{}\n", d))
+            .collect()),
+        Some(name) => {
+            let dimension = Dimension::iter()
+                .find(|d| d.to_string().eq_ignore_ascii_case(name))
+                .ok_or_else(|| format!("Unknown dimension '{}'", name))?;
+            Ok(get_units(&dimension).iter()
+                .map(|u| format!("{:<28} {:<12} {:?}\n", u.name, u.symbol, u.system))
+                .collect())
+        }
+    }
+}
+
+/// Format a value split between several units, largest first, e.g. `6 ft 0 in`.
+/// All but the last unit take whole numbers, the last is rounded to a whole number.
+pub(crate) fn format_compound(value: f64, from: &Unit, units: &[&'static Unit]) -> String {
+    let Some(smallest) = units.last() else {
+        return String::new();
+    };
+    let mut remaining = convert(&value.abs(), from, smallest).round();
+    let mut parts = Vec::new();
+    for unit in units {
+        let size = convert(&1.0, unit, smallest).round();
+        let count = if size > 0.0 { (remaining / size).floor() } else { 0.0 };
+        remaining -= count * size;
+        parts.push(format!("{} {}", count, unit.symbol));
+    }
+    let sign = if value < 0.0 && parts.iter().any(|p| !p.starts_with("0 ")) { "-" } else { "" };
+    format!("{}{}", sign, parts.join(" "))
+}
+
 #[cfg(test)]
 mod tests {
     use crate::assert_near;
-    use crate::conversions::convert;
+    use crate::conversions::{convert, Dimension, find_unit, get_all_units, get_to_units, get_units, list_units, round_significant, System, try_convert};
+    use crate::conversions::area::{SQ_FOOT, SQ_INCH};
+    use crate::conversions::length::{FOOT, INCH, KILOMETRE, METRE, MILE, YARD};
+    use crate::conversions::temperature::{FAHRENHEIT, RANKINE};
+    use crate::conversions::time::SECOND;
+    use crate::conversions::volume::{IMP_FL_OUNCE, IMP_GALLON, IMP_PINT};
     use crate::conversions::mass::*;
 
     #[test]
@@ -240,4 +427,78 @@ mod tests {
         assert_near!(convert(&1.0, &TONNE, &TON), 0.984207);
         assert_eq!(convert(&2.0, &TON, &TON_SHORT), 2.240);
     }
+    #[test]
+    fn test_round_significant() {
+        assert_eq!(round_significant(1234.5, 3), 1230.0);
+        assert_eq!(round_significant(0.012345, 2), 0.012);
+        assert_eq!(round_significant(-98.76, 2), -99.0);
+        assert_eq!(round_significant(0.0, 3), 0.0);
+        assert_eq!(round_significant(1.5, 0), 1.5);
+    }
+    #[test]
+    fn test_try_convert_rounded() {
+        assert_eq!(try_convert(&1.23456, &Some(&KILOMETRE), &Some(&METRE), Some(3)), 1230.0);
+        assert_near!(try_convert(&1.23456, &Some(&KILOMETRE), &Some(&METRE), None), 1234.56);
+        assert_near!(try_convert(&26.2188, &Some(&MILE), &Some(&KILOMETRE), Some(3)), 42.1648, 0.0001);
+        assert_eq!(try_convert(&1.0, &None, &Some(&METRE), Some(3)), 0.0);
+    }
+    #[test]
+    fn test_find_unit() {
+        assert_eq!(find_unit("Mile"), Some(&MILE));
+        assert_eq!(find_unit("Short Ton"), Some(&TON_SHORT));
+        assert_eq!(find_unit("Furlongs per fortnight"), None);
+    }
+    #[test]
+    fn test_exact_system_conversions() {
+        assert_eq!(convert(&24.0, &INCH, &FOOT), 2.0);
+        assert_eq!(convert(&36.0, &INCH, &YARD), 1.0);
+        assert_eq!(convert(&5280.0, &FOOT, &MILE), 1.0);
+        assert_eq!(convert(&1.0, &MILE, &INCH), 63360.0);
+        assert_eq!(convert(&convert(&7.0, &FOOT, &INCH), &INCH, &FOOT), 7.0);
+        assert_eq!(convert(&convert(&3.0, &MILE, &FOOT), &FOOT, &MILE), 3.0);
+        assert_eq!(convert(&40.0, &OUNCE, &POUND), 2.5);
+        assert_eq!(convert(&convert(&11.0, &TON_SHORT, &OUNCE), &OUNCE, &TON_SHORT), 11.0);
+        assert_eq!(convert(&1.0, &IMP_GALLON, &IMP_PINT), 8.0);
+        assert_eq!(convert(&3.0, &IMP_PINT, &IMP_FL_OUNCE), 60.0);
+    }
+
+    #[test]
+    fn test_system_factors() {
+        // Units of a system are either a multiple of its base unit, or convert with functions
+        for unit in get_all_units() {
+            if unit.system_factor.is_some() {
+                assert!(unit.to_system_base.is_none() && unit.from_system_base.is_none(), "{} has a factor and functions", unit.name);
+            } else {
+                assert!(unit.system.is_default() || unit.to_system_base.is_some(), "{} has no system factor", unit.name);
+            }
+        }
+        assert_eq!(convert(&3.0, &FOOT, &INCH), 36.0);
+        assert_eq!(convert(&1.0, &SQ_FOOT, &SQ_INCH), 144.0);
+        assert_near!(convert(&32.0, &FAHRENHEIT, &RANKINE), 491.67);
+        assert_near!(convert(&491.67, &RANKINE, &FAHRENHEIT), 32.0);
+    }
+
+    #[test]
+    fn test_get_to_units() {
+        let all = get_to_units(&MILE, false);
+        assert!(all.contains(&&FOOT));
+        let others = get_to_units(&MILE, true);
+        assert!(!others.contains(&&FOOT));
+        assert!(!others.contains(&&MILE));
+        assert!(others.contains(&&KILOMETRE));
+        assert!(others.iter().all(|u| u.system != System::Imperial));
+        // Every unit of time is metric, so there are none to leave out
+        assert_eq!(get_to_units(&SECOND, true).len(), get_units(&Dimension::Time).len());
+    }
+
+    #[test]
+    fn test_list_units() {
+        let dimensions = list_units(None).unwrap();
+        assert!(dimensions.starts_with("Length\nArea\n"));
+        let units = list_units(Some("density")).unwrap();
+        assert_eq!(units.lines().count(), 4);
+        assert_eq!(units.lines().next(), Some("Kilogram per Cubic Metre     kg/m³        Metric"));
+        assert!(units.contains("Pound per Cubic Foot"));
+        assert_eq!(list_units(Some("Colour")), Err("Unknown dimension 'Colour'".to_string()));
+    }
 }
@@ -25,55 +25,102 @@ use crate::conversions::{Dimension, System, Unit};
 // Power unit constants
 pub static WATT: Unit = Unit {
     name: "Watt",
+    symbol: "W",
     dimension: Dimension::Power,
     system: System::Metric,
     to_base: None,
     from_base: None,
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static KILOWATT: Unit = Unit {
     name: "Kilowatt",
+    symbol: "kW",
     dimension: Dimension::Power,
     system: System::Metric,
     to_base: Some(Unit::from_kilo),
     from_base: Some(Unit::to_kilo),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static MEGAWATT: Unit = Unit {
     name: "Megawatt",
+    symbol: "MW",
     dimension: Dimension::Power,
     system: System::Metric,
     to_base: Some(Unit::from_mega),
     from_base: Some(Unit::to_mega),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static GIGAWATT: Unit = Unit {
     name: "Gigawatt",
+    symbol: "GW",
     dimension: Dimension::Power,
     system: System::Metric,
     to_base: Some(Unit::from_giga),
     from_base: Some(Unit::to_giga),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
+};
+static WATTS_PER_METRIC_HORSEPOWER: f64 = 735.49875;
+pub static METRIC_HORSEPOWER: Unit = Unit {
+    name: "Metric Horsepower",
+    symbol: "PS",
+    dimension: Dimension::Power,
+    system: System::Metric,
+    to_base: Some(|v| v * WATTS_PER_METRIC_HORSEPOWER),
+    from_base: Some(|v| v / WATTS_PER_METRIC_HORSEPOWER),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
 };
 
-// Imperial units
+// Imperial units, which convert among themselves through the horsepower
+static WATTS_PER_HORSEPOWER: f64 = 745.699872;
+static WATTS_PER_BTU_PER_HOUR: f64 = 1055.05585262 / 3600.0;
 pub static HORSEPOWER: Unit = Unit {
     name: "Horsepower",
+    symbol: "hp",
+    dimension: Dimension::Power,
+    system: System::Imperial,
+    to_base: Some(|v| v * WATTS_PER_HORSEPOWER),
+    from_base: Some(|v| v / WATTS_PER_HORSEPOWER),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
+};
+pub static BTU_PER_HOUR: Unit = Unit {
+    name: "BTU per hour",
+    symbol: "BTU/h",
+    dimension: Dimension::Power,
+    system: System::Imperial,
+    to_base: Some(|v| v * WATTS_PER_BTU_PER_HOUR),
+    from_base: Some(|v| v / WATTS_PER_BTU_PER_HOUR),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((WATTS_PER_BTU_PER_HOUR, WATTS_PER_HORSEPOWER)),
+};
+/// One horsepower is 550 foot-pounds per second.
+pub static FOOT_POUND_PER_SECOND: Unit = Unit {
+    name: "Foot-pound per second",
+    symbol: "ft·lbf/s",
     dimension: Dimension::Power,
     system: System::Imperial,
-    to_base: Some(|v| v * 745.699872),
-    from_base: Some(|v| v / 745.699872),
+    to_base: Some(|v| v * WATTS_PER_HORSEPOWER / 550.0),
+    from_base: Some(|v| v * 550.0 / WATTS_PER_HORSEPOWER),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((1.0, 550.0)),
 };
 
 pub(crate) fn get_all() -> Vec<&'static Unit> {
-    vec![&WATT, &KILOWATT, &MEGAWATT, &GIGAWATT,
-         &HORSEPOWER,
+    vec![&WATT, &KILOWATT, &MEGAWATT, &GIGAWATT, &METRIC_HORSEPOWER,
+         &HORSEPOWER, &BTU_PER_HOUR, &FOOT_POUND_PER_SECOND,
     ]
 }
 
@@ -81,19 +128,23 @@ pub(crate) fn get_all() -> Vec<&'static Unit> {
 mod tests {
     use crate::assert_near;
     use crate::conversions::convert;
-    use crate::conversions::power::{GIGAWATT, HORSEPOWER, KILOWATT, MEGAWATT, WATT};
+    use crate::conversions::power::{BTU_PER_HOUR, FOOT_POUND_PER_SECOND, GIGAWATT, HORSEPOWER, KILOWATT, MEGAWATT, METRIC_HORSEPOWER, WATT};
 
     #[test]
     fn test_metric_power_units() {
         assert_near!(convert(&1.0, &KILOWATT, &WATT), 1000.0);
         assert_near!(convert(&1.0, &MEGAWATT, &WATT), 1_000_000.0);
         assert_near!(convert(&1.0, &GIGAWATT, &WATT), 1_000_000_000.0);
+        assert_near!(convert(&1.0, &METRIC_HORSEPOWER, &WATT), 735.49875);
     }
 
     #[test]
     fn test_imperial_power_units() {
         assert_near!(convert(&1.0, &HORSEPOWER, &WATT), 745.699872);
         assert_near!(convert(&10.0, &HORSEPOWER, &KILOWATT), 7.45699872);
+        assert_near!(convert(&1.0, &BTU_PER_HOUR, &WATT), 0.293071);
+        assert_near!(convert(&1.0, &HORSEPOWER, &FOOT_POUND_PER_SECOND), 550.0);
+        assert_near!(convert(&1.0, &HORSEPOWER, &BTU_PER_HOUR), 2544.4336, 0.0001);
     }
 
     #[test]
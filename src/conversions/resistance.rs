@@ -0,0 +1,87 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use crate::conversions::{Dimension, System, Unit};
+
+// Resistance unit constants, all metric with SI prefixes on the base unit
+pub static OHM: Unit = Unit {
+    name: "Ohm",
+    symbol: "Ω",
+    dimension: Dimension::Resistance,
+    system: System::Metric,
+    to_base: None,
+    from_base: None,
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MILLIOHM: Unit = Unit {
+    name: "Milliohm",
+    symbol: "mΩ",
+    dimension: Dimension::Resistance,
+    system: System::Metric,
+    to_base: Some(Unit::from_milli),
+    from_base: Some(Unit::to_milli),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static KILOHM: Unit = Unit {
+    name: "Kilohm",
+    symbol: "kΩ",
+    dimension: Dimension::Resistance,
+    system: System::Metric,
+    to_base: Some(Unit::from_kilo),
+    from_base: Some(Unit::to_kilo),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MEGOHM: Unit = Unit {
+    name: "Megohm",
+    symbol: "MΩ",
+    dimension: Dimension::Resistance,
+    system: System::Metric,
+    to_base: Some(Unit::from_mega),
+    from_base: Some(Unit::to_mega),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+
+pub(crate) fn get_all() -> Vec<&'static Unit> {
+    vec![&OHM, &MILLIOHM, &KILOHM, &MEGOHM]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_near;
+    use crate::conversions::convert;
+    use crate::conversions::resistance::{KILOHM, MEGOHM, MILLIOHM, OHM};
+
+    #[test]
+    fn test_resistance_units() {
+        assert_near!(convert(&1.0, &KILOHM, &OHM), 1000.0);
+        assert_near!(convert(&4.7, &MEGOHM, &KILOHM), 4700.0);
+        assert_near!(convert(&500.0, &MILLIOHM, &OHM), 0.5);
+    }
+}
@@ -0,0 +1,99 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+// A slope can be given as an angle from the horizontal, a percentage grade or a ratio of rise to
+// run written 1:n. They aren't linear in each other (grade = tan(angle) * 100), so they can't be
+// units of a dimension and are related here instead.
+
+/// The ways a slope can be given.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum SlopeField {
+    /// The angle from the horizontal in degrees
+    Angle,
+    /// The rise as a percentage of the run
+    Grade,
+    /// The n of a ratio 1:n, the run for each unit of rise
+    Ratio,
+}
+
+/// A slope, kept as its angle in radians. Slopes going down are negative.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Slope {
+    radians: f64,
+}
+
+impl Slope {
+    /// The slope given by `value` in the way given by `field`.
+    pub(crate) fn from_field(field: SlopeField, value: f64) -> Result<Slope, String> {
+        if !value.is_finite() {
+            return Err(String::from("Invalid slope"));
+        }
+        let radians = match field {
+            SlopeField::Angle if value.abs() >= 90.0 => return Err(String::from("The angle must be less than 90°")),
+            SlopeField::Angle => value.to_radians(),
+            SlopeField::Grade => (value / 100.0).atan(),
+            SlopeField::Ratio if value == 0.0 => return Err(String::from("A ratio of 1:0 is vertical")),
+            SlopeField::Ratio => (1.0 / value).atan(),
+        };
+        Ok(Slope { radians })
+    }
+
+    /// The slope in the way given by `field`. A flat slope has an infinite ratio.
+    pub(crate) fn value(&self, field: SlopeField) -> f64 {
+        match field {
+            SlopeField::Angle => self.radians.to_degrees(),
+            SlopeField::Grade => self.radians.tan() * 100.0,
+            SlopeField::Ratio => 1.0 / self.radians.tan(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_near;
+    use crate::conversions::slope::*;
+
+    #[test]
+    fn test_slope() {
+        let slope = Slope::from_field(SlopeField::Angle, 45.0).unwrap();
+        assert_near!(slope.value(SlopeField::Grade), 100.0);
+        assert_near!(slope.value(SlopeField::Ratio), 1.0);
+
+        let slope = Slope::from_field(SlopeField::Grade, 100.0).unwrap();
+        assert_near!(slope.value(SlopeField::Angle), 45.0);
+        let slope = Slope::from_field(SlopeField::Ratio, 1.0).unwrap();
+        assert_near!(slope.value(SlopeField::Angle), 45.0);
+
+        // A wheelchair ramp
+        let slope = Slope::from_field(SlopeField::Ratio, 12.0).unwrap();
+        assert_near!(slope.value(SlopeField::Grade), 8.333333);
+        assert_near!(slope.value(SlopeField::Angle), 4.763642);
+
+        let slope = Slope::from_field(SlopeField::Grade, -5.0).unwrap();
+        assert_near!(slope.value(SlopeField::Ratio), -20.0);
+        assert!(Slope::from_field(SlopeField::Grade, 0.0).unwrap().value(SlopeField::Ratio).is_infinite());
+
+        assert!(Slope::from_field(SlopeField::Angle, 90.0).is_err());
+        assert!(Slope::from_field(SlopeField::Ratio, 0.0).is_err());
+        assert!(Slope::from_field(SlopeField::Grade, f64::NAN).is_err());
+    }
+}
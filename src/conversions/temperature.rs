@@ -27,36 +27,60 @@ use crate::conversions::{Dimension, System, Unit};
 
 pub static CELSIUS: Unit = Unit {
     name: "Celsius",
+    symbol: "°C",
     dimension: Dimension::Temp,
     system: System::Metric,
     to_base: None,
     from_base: None,
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 
 pub static KELVIN: Unit = Unit {
     name: "Kelvin",
+    symbol: "K",
     dimension: Dimension::Temp,
     system: System::Metric,
     to_base: Some(|v| v - 273.15),
     from_base: Some(|v| v + 273.15),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 
+// Imperial units
+// Fahrenheit is the base of the imperial system. Temperature scales are offset from each other,
+// not just scaled, so imperial units must convert to Fahrenheit rather than assume a
+// multiplicative factor.
+
 pub static FAHRENHEIT: Unit = Unit {
     name: "Fahrenheit",
+    symbol: "°F",
     dimension: Dimension::Temp,
-    system: System::Metric,
-    to_base: Some(|v| (v - 32.0) / 9.0 * 5.0),
-    from_base: Some(|v| v / 5.0 * 9.0 + 32.0),
+    system: System::Imperial,
+    // The offset applies on the Fahrenheit side, so it is subtracted before scaling and added after
+    to_base: Some(|v| ((v - 32.0) / 9.0) * 5.0),
+    from_base: Some(|v| ((v / 5.0) * 9.0) + 32.0),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
+};
+
+pub static RANKINE: Unit = Unit {
+    name: "Rankine",
+    symbol: "°R",
+    dimension: Dimension::Temp,
+    system: System::Imperial,
+    to_base: Some(|v| (v - 491.67) / 1.8),
+    from_base: Some(|v| v * 1.8 + 491.67),
+    to_system_base: Some(|v| v - 459.67),
+    from_system_base: Some(|v| v + 459.67),
+    system_factor: None,
 };
 
 pub(crate) fn get_all() -> Vec<&'static Unit> {
-    vec![&CELSIUS, &KELVIN, &FAHRENHEIT,
+    vec![&CELSIUS, &KELVIN, &FAHRENHEIT, &RANKINE,
     ]
 }
 
@@ -77,4 +101,44 @@ mod tests {
         assert_near!(convert(&550.0, &KELVIN, &CELSIUS), 276.85);
         assert_near!(convert(&70.0, &FAHRENHEIT, &KELVIN), 294.2611111);
     }
+    #[test]
+    fn test_rankine() {
+        assert_near!(convert(&491.67, &RANKINE, &CELSIUS), 0.0);
+        assert_near!(convert(&0.0, &CELSIUS, &RANKINE), 491.67);
+        assert_near!(convert(&32.0, &FAHRENHEIT, &RANKINE), 491.67);
+        assert_near!(convert(&491.67, &RANKINE, &FAHRENHEIT), 32.0);
+        assert_near!(convert(&100.0, &KELVIN, &RANKINE), 180.0);
+        assert_near!(convert(&180.0, &RANKINE, &KELVIN), 100.0);
+        assert_near!(convert(&convert(&273.15, &KELVIN, &RANKINE), &RANKINE, &KELVIN), 273.15);
+    }
+
+    #[test]
+    fn test_fahrenheit_table() {
+        let table = [
+            // (Fahrenheit, Celsius)
+            (-459.67, -273.15), // Absolute zero
+            (-40.0, -40.0),
+            (0.0, -17.7777778),
+            (32.0, 0.0), // Freezing
+            (50.0, 10.0),
+            (98.6, 37.0), // Body temperature
+            (212.0, 100.0), // Boiling
+            (451.0, 232.7777778),
+        ];
+        for (f, c) in table {
+            assert_near!(convert(&f, &FAHRENHEIT, &CELSIUS), c);
+            assert_near!(convert(&c, &CELSIUS, &FAHRENHEIT), f);
+        }
+    }
+
+    #[test]
+    fn test_round_trips() {
+        for units in [&CELSIUS, &KELVIN, &FAHRENHEIT, &RANKINE].windows(2) {
+            let mut t = -500.0;
+            while t <= 5000.0 {
+                assert_near!(convert(&convert(&t, units[0], units[1]), units[1], units[0]), t, 1e-9);
+                t += 12.5;
+            }
+        }
+    }
 }
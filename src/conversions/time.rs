@@ -0,0 +1,198 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use crate::conversions::{Dimension, System, Unit};
+
+// Time unit constants
+// Time has no separate imperial units, so everything is Metric and converts to the second.
+
+pub static SECOND: Unit = Unit {
+    name: "Second",
+    symbol: "s",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: None,
+    from_base: None,
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static NANOSECOND: Unit = Unit {
+    name: "Nanosecond",
+    symbol: "ns",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(Unit::from_nano),
+    from_base: Some(Unit::to_nano),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MICROSECOND: Unit = Unit {
+    name: "Microsecond",
+    symbol: "µs",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(Unit::from_micro),
+    from_base: Some(Unit::to_micro),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MILLISECOND: Unit = Unit {
+    name: "Millisecond",
+    symbol: "ms",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(Unit::from_milli),
+    from_base: Some(Unit::to_milli),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+static SECONDS_PER_MINUTE: f64 = 60.0;
+pub static MINUTE: Unit = Unit {
+    name: "Minute",
+    symbol: "min",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(|v| v * SECONDS_PER_MINUTE),
+    from_base: Some(|v| v / SECONDS_PER_MINUTE),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+static SECONDS_PER_HOUR: f64 = 3600.0;
+pub static HOUR: Unit = Unit {
+    name: "Hour",
+    symbol: "h",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(|v| v * SECONDS_PER_HOUR),
+    from_base: Some(|v| v / SECONDS_PER_HOUR),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+static SECONDS_PER_DAY: f64 = 86400.0;
+pub static DAY: Unit = Unit {
+    name: "Day",
+    symbol: "d",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(|v| v * SECONDS_PER_DAY),
+    from_base: Some(|v| v / SECONDS_PER_DAY),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static WEEK: Unit = Unit {
+    name: "Week",
+    symbol: "wk",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(|v| v * SECONDS_PER_DAY * 7.0),
+    from_base: Some(|v| v / (SECONDS_PER_DAY * 7.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+
+// We use the Julian year of exactly 365.25 days. It is the year astronomers use (a lightyear is
+// defined with it) and avoids having to choose between common and leap years.
+static SECONDS_PER_YEAR: f64 = 365.25 * 86400.0;
+pub static YEAR: Unit = Unit {
+    name: "Year",
+    symbol: "yr",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(|v| v * SECONDS_PER_YEAR),
+    from_base: Some(|v| v / SECONDS_PER_YEAR),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static DECADE: Unit = Unit {
+    name: "Decade",
+    symbol: "decade",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(|v| v * SECONDS_PER_YEAR * 10.0),
+    from_base: Some(|v| v / (SECONDS_PER_YEAR * 10.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static CENTURY: Unit = Unit {
+    name: "Century",
+    symbol: "century",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(|v| v * SECONDS_PER_YEAR * 100.0),
+    from_base: Some(|v| v / (SECONDS_PER_YEAR * 100.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MILLENNIUM: Unit = Unit {
+    name: "Millennium",
+    symbol: "millennium",
+    dimension: Dimension::Time,
+    system: System::Metric,
+    to_base: Some(|v| v * SECONDS_PER_YEAR * 1000.0),
+    from_base: Some(|v| v / (SECONDS_PER_YEAR * 1000.0)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+
+pub(crate) fn get_all() -> Vec<&'static Unit> {
+    vec![&NANOSECOND, &MICROSECOND, &MILLISECOND, &SECOND,
+         &MINUTE, &HOUR, &DAY, &WEEK,
+         &YEAR, &DECADE, &CENTURY, &MILLENNIUM,
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_near;
+    use crate::conversions::convert;
+    use crate::conversions::time::*;
+
+    #[test]
+    fn test_short_times() {
+        assert_near!(convert(&1.0, &SECOND, &NANOSECOND), 1e9);
+        assert_near!(convert(&1500.0, &NANOSECOND, &MICROSECOND), 1.5);
+        assert_near!(convert(&1.0, &MILLISECOND, &MICROSECOND), 1000.0);
+        assert_near!(convert(&90.0, &MINUTE, &HOUR), 1.5);
+        assert_near!(convert(&2.0, &WEEK, &DAY), 14.0);
+    }
+
+    #[test]
+    fn test_long_times() {
+        assert_near!(convert(&1.0, &YEAR, &SECOND), 31557600.0);
+        assert_near!(convert(&1.0, &YEAR, &DAY), 365.25);
+        assert_near!(convert(&1.0, &CENTURY, &YEAR), 100.0);
+        assert_near!(convert(&1.0, &MILLENNIUM, &CENTURY), 10.0);
+        assert_near!(convert(&3.0, &DECADE, &YEAR), 30.0);
+    }
+}
@@ -25,32 +25,38 @@ use crate::conversions::{Dimension, System, Unit};
 // Torque unit constants
 pub static NEWTON_METRE: Unit = Unit {
     name: "newton_metre",
+    symbol: "N·m",
     dimension: Dimension::Torque,
     system: System::Metric,
     to_base: None,
     from_base: None,
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 
 // Imperial units
 pub static FOOT_POUND: Unit = Unit {
     name: "foot_pound",
+    symbol: "lbf·ft",
     dimension: Dimension::Torque,
     system: System::Imperial,
     to_base: Some(|v| v * 1.3558179483314),
     from_base: Some(|v| v / 1.3558179483314),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
 };
 pub static INCH_POUND: Unit = Unit {
     name: "inch_pound",
+    symbol: "lbf·in",
     dimension: Dimension::Torque,
     system: System::Imperial,
     to_base: Some(|v| v * 0.1129848290276167),
     from_base: Some(|v| v / 0.1129848290276167),
-    to_system_base: Some(|v| v / 12.0),
-    from_system_base: Some(|v| v * 12.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 12.0)),
 };
 
 pub(crate) fn get_all() -> Vec<&'static Unit> {
@@ -0,0 +1,87 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use crate::conversions::{Dimension, System, Unit};
+
+// Voltage unit constants, all metric with SI prefixes on the base unit
+pub static VOLT: Unit = Unit {
+    name: "Volt",
+    symbol: "V",
+    dimension: Dimension::Voltage,
+    system: System::Metric,
+    to_base: None,
+    from_base: None,
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MILLIVOLT: Unit = Unit {
+    name: "Millivolt",
+    symbol: "mV",
+    dimension: Dimension::Voltage,
+    system: System::Metric,
+    to_base: Some(Unit::from_milli),
+    from_base: Some(Unit::to_milli),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static MICROVOLT: Unit = Unit {
+    name: "Microvolt",
+    symbol: "µV",
+    dimension: Dimension::Voltage,
+    system: System::Metric,
+    to_base: Some(Unit::from_micro),
+    from_base: Some(Unit::to_micro),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static KILOVOLT: Unit = Unit {
+    name: "Kilovolt",
+    symbol: "kV",
+    dimension: Dimension::Voltage,
+    system: System::Metric,
+    to_base: Some(Unit::from_kilo),
+    from_base: Some(Unit::to_kilo),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+
+pub(crate) fn get_all() -> Vec<&'static Unit> {
+    vec![&VOLT, &MILLIVOLT, &MICROVOLT, &KILOVOLT]
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_near;
+    use crate::conversions::convert;
+    use crate::conversions::voltage::{KILOVOLT, MICROVOLT, MILLIVOLT, VOLT};
+
+    #[test]
+    fn test_voltage_units() {
+        assert_near!(convert(&1.0, &KILOVOLT, &VOLT), 1000.0);
+        assert_near!(convert(&3300.0, &MILLIVOLT, &VOLT), 3.3);
+        assert_near!(convert(&1.0, &MILLIVOLT, &MICROVOLT), 1000.0);
+    }
+}
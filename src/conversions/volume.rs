@@ -28,162 +28,328 @@ use crate::conversions::{Dimension, System, Unit};
 
 pub static LITRE: Unit = Unit {
     name: "Litre",
+    symbol: "L",
     dimension: Dimension::Volume,
     system: System::Metric,
     to_base: None,
     from_base: None,
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static CU_METRE: Unit = Unit {
     name: "Cubic Metre",
+    symbol: "m³",
     dimension: Dimension::Volume,
     system: System::Metric,
     to_base: Some(|v| v * 1000.0),
     from_base: Some(|v| v / 1000.0),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static CUBIC_CENTIMETRE: Unit = Unit {
     name: "CC",
+    symbol: "cm³",
     dimension: Dimension::Volume,
     system: System::Metric,
     to_base: Some(|v| v / 1000.0),
     from_base: Some(|v| v * 1000.0),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 pub static CUBIC_KILOMETRE: Unit = Unit {
     name: "Cubic Kilometre",
+    symbol: "km³",
     dimension: Dimension::Volume,
     system: System::Metric,
     to_base: Some(|v| v * 1_000_000.0 * 1_000_000.0),
     from_base: Some(|v| v / (1_000_000.0 * 1_000_000.0)),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 
 pub static KILO_LITRE: Unit = Unit {
     name: "Kilo Litre",
+    symbol: "kL",
     dimension: Dimension::Volume,
     system: System::Metric,
     to_base: Some(Unit::from_kilo),
     from_base: Some(Unit::to_kilo),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 
 pub static MEGA_LITRE: Unit = Unit {
     name: "Mega Litre",
+    symbol: "ML",
     dimension: Dimension::Volume,
     system: System::Metric,
     to_base: Some(Unit::from_mega),
     from_base: Some(Unit::to_mega),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
 };
 
 pub static GIGA_LITRE: Unit = Unit {
     name: "Giga Litre",
+    symbol: "GL",
     dimension: Dimension::Volume,
     system: System::Metric,
     to_base: Some(Unit::from_giga),
     from_base: Some(Unit::to_giga),
     to_system_base: None,
     from_system_base: None,
+    system_factor: None,
+};
+
+// Metric cooking units
+pub static METRIC_CUP: Unit = Unit {
+    name: "Metric Cup",
+    symbol: "metric cup",
+    dimension: Dimension::Volume,
+    system: System::Metric,
+    to_base: Some(|v| v * 0.25),
+    from_base: Some(|v| v / 0.25),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static METRIC_TABLESPOON: Unit = Unit {
+    name: "Metric Tablespoon",
+    symbol: "metric tbsp",
+    dimension: Dimension::Volume,
+    system: System::Metric,
+    to_base: Some(|v| v * 15.0 / 1000.0),
+    from_base: Some(|v| v * 1000.0 / 15.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
+};
+pub static METRIC_TEASPOON: Unit = Unit {
+    name: "Metric Teaspoon",
+    symbol: "metric tsp",
+    dimension: Dimension::Volume,
+    system: System::Metric,
+    to_base: Some(|v| v * 5.0 / 1000.0),
+    from_base: Some(|v| v * 1000.0 / 5.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: None,
 };
 
 // Imperial units
 
 pub static IMP_FL_OUNCE: Unit = Unit {
     name: "Imp Fl Ounce",
+    symbol: "imp fl oz",
     dimension: Dimension::Volume,
     system: System::Imperial,
     to_base: Some(|v| v / 35.19507973),
     from_base: Some(|v| v * 35.19507973),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
 };
 pub static IMP_CUBIC_INCH: Unit = Unit {
     name: "Cubic Inch",
+    symbol: "in³",
     dimension: Dimension::Volume,
     system: System::Imperial,
     to_base: Some(|v| v / 61.02374409),
     from_base: Some(|v| v * 61.02374409),
-    to_system_base: Some(|v| v * 1.733871455),
-    from_system_base: Some(|v| v * 1.733871455),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 1.733871455)),
 };
 pub static IMP_PINT: Unit = Unit {
     name: "Imp Pint",
+    symbol: "imp pt",
     dimension: Dimension::Volume,
     system: System::Imperial,
     to_base: Some(|v| v / 1.759753986),
     from_base: Some(|v| v * 1.759753986),
-    to_system_base: Some(|v| v * 20.0),
-    from_system_base: Some(|v| v / 20.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((20.0, 1.0)),
 };
 pub static IMP_QUART: Unit = Unit {
     name: "Imp Quart",
+    symbol: "imp qt",
     dimension: Dimension::Volume,
     system: System::Imperial,
     to_base: Some(|v| v / 0.8798769932),
     from_base: Some(|v| v * 0.8798769932),
-    to_system_base: Some(|v| v * 40.0),
-    from_system_base: Some(|v| v / 40.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((40.0, 1.0)),
 };
 pub static IMP_GALLON: Unit = Unit {
     name: "Imp Gallon",
+    symbol: "imp gal",
     dimension: Dimension::Volume,
     system: System::Imperial,
     to_base: Some(|v| v / 0.2199692483),
     from_base: Some(|v| v * 0.2199692483),
-    to_system_base: Some(|v| v * 160.0),
-    from_system_base: Some(|v| v / 160.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((160.0, 1.0)),
 };
 
 // US units
 pub static US_FL_OUNCE: Unit = Unit {
     name: "US Fl Ounce",
+    symbol: "US fl oz",
     dimension: Dimension::Volume,
     system: System::US,
     to_base: Some(|v| v / 33.81402270),
     from_base: Some(|v| v * 33.81402270),
     to_system_base: None,
     from_system_base: None,
+    system_factor: Some((1.0, 1.0)),
 };
 pub static US_PINT: Unit = Unit {
     name: "US Pint",
+    symbol: "US pt",
     dimension: Dimension::Volume,
     system: System::US,
     to_base: Some(|v| v / 2.113376419),
     from_base: Some(|v| v * 2.113376419),
-    to_system_base: Some(|v| v * 16.0),
-    from_system_base: Some(|v| v / 16.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((16.0, 1.0)),
 };
 pub static US_QUART: Unit = Unit {
     name: "US Quart",
+    symbol: "US qt",
     dimension: Dimension::Volume,
     system: System::US,
     to_base: Some(|v| v / 1.056688209),
     from_base: Some(|v| v * 1.056688209),
-    to_system_base: Some(|v| v * 32.0),
-    from_system_base: Some(|v| v / 32.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((32.0, 1.0)),
 };
 pub static US_GALLON: Unit = Unit {
     name: "US Gallon",
+    symbol: "US gal",
     dimension: Dimension::Volume,
     system: System::US,
     to_base: Some(|v| v / 0.2641720524),
     from_base: Some(|v| v * 0.2641720524),
-    to_system_base: Some(|v| v * 128.0),
-    from_system_base: Some(|v| v / 128.0),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((128.0, 1.0)),
+};
+
+// US cooking units
+pub static US_CUP: Unit = Unit {
+    name: "US Cup",
+    symbol: "US cup",
+    dimension: Dimension::Volume,
+    system: System::US,
+    to_base: Some(|v| v * 8.0 * MILLILITRES_PER_US_FL_OUNCE / 1000.0),
+    from_base: Some(|v| v * 1000.0 / (8.0 * MILLILITRES_PER_US_FL_OUNCE)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((8.0, 1.0)),
+};
+pub static US_TABLESPOON: Unit = Unit {
+    name: "US Tablespoon",
+    symbol: "US tbsp",
+    dimension: Dimension::Volume,
+    system: System::US,
+    to_base: Some(|v| v * MILLILITRES_PER_US_FL_OUNCE / 2000.0),
+    from_base: Some(|v| v * 2000.0 / MILLILITRES_PER_US_FL_OUNCE),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 2.0)),
+};
+pub static US_TEASPOON: Unit = Unit {
+    name: "US Teaspoon",
+    symbol: "US tsp",
+    dimension: Dimension::Volume,
+    system: System::US,
+    to_base: Some(|v| v * MILLILITRES_PER_US_FL_OUNCE / 6000.0),
+    from_base: Some(|v| v * 6000.0 / MILLILITRES_PER_US_FL_OUNCE),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((1.0, 6.0)),
+};
+
+// US dry units
+// These are defined from the dry pint, which is an exact number of millilitres. Within the US system
+// they convert through the fluid ounce like the other US units.
+static MILLILITRES_PER_US_FL_OUNCE: f64 = 29.5735295625;
+static MILLILITRES_PER_US_DRY_PINT: f64 = 550.6104713575;
+pub static US_DRY_PINT: Unit = Unit {
+    name: "US Dry Pint",
+    symbol: "US dry pt",
+    dimension: Dimension::Volume,
+    system: System::US,
+    to_base: Some(|v| v * MILLILITRES_PER_US_DRY_PINT / 1000.0),
+    from_base: Some(|v| v * 1000.0 / MILLILITRES_PER_US_DRY_PINT),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((MILLILITRES_PER_US_DRY_PINT, MILLILITRES_PER_US_FL_OUNCE)),
+};
+pub static US_DRY_QUART: Unit = Unit {
+    name: "US Dry Quart",
+    symbol: "US dry qt",
+    dimension: Dimension::Volume,
+    system: System::US,
+    to_base: Some(|v| v * 2.0 * MILLILITRES_PER_US_DRY_PINT / 1000.0),
+    from_base: Some(|v| v * 1000.0 / (2.0 * MILLILITRES_PER_US_DRY_PINT)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((2.0 * MILLILITRES_PER_US_DRY_PINT, MILLILITRES_PER_US_FL_OUNCE)),
+};
+pub static US_DRY_GALLON: Unit = Unit {
+    name: "US Dry Gallon",
+    symbol: "US dry gal",
+    dimension: Dimension::Volume,
+    system: System::US,
+    to_base: Some(|v| v * 8.0 * MILLILITRES_PER_US_DRY_PINT / 1000.0),
+    from_base: Some(|v| v * 1000.0 / (8.0 * MILLILITRES_PER_US_DRY_PINT)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((8.0 * MILLILITRES_PER_US_DRY_PINT, MILLILITRES_PER_US_FL_OUNCE)),
+};
+pub static US_PECK: Unit = Unit {
+    name: "US Peck",
+    symbol: "pk",
+    dimension: Dimension::Volume,
+    system: System::US,
+    to_base: Some(|v| v * 16.0 * MILLILITRES_PER_US_DRY_PINT / 1000.0),
+    from_base: Some(|v| v * 1000.0 / (16.0 * MILLILITRES_PER_US_DRY_PINT)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((16.0 * MILLILITRES_PER_US_DRY_PINT, MILLILITRES_PER_US_FL_OUNCE)),
+};
+pub static US_BUSHEL: Unit = Unit {
+    name: "US Bushel",
+    symbol: "bu",
+    dimension: Dimension::Volume,
+    system: System::US,
+    to_base: Some(|v| v * 64.0 * MILLILITRES_PER_US_DRY_PINT / 1000.0),
+    from_base: Some(|v| v * 1000.0 / (64.0 * MILLILITRES_PER_US_DRY_PINT)),
+    to_system_base: None,
+    from_system_base: None,
+    system_factor: Some((64.0 * MILLILITRES_PER_US_DRY_PINT, MILLILITRES_PER_US_FL_OUNCE)),
 };
 
 pub(crate) fn get_all() -> Vec<&'static Unit> {
     vec![&LITRE, &CU_METRE, &CUBIC_CENTIMETRE, &CUBIC_KILOMETRE,
-         &KILO_LITRE, &MEGA_LITRE, &GIGA_LITRE, &IMP_CUBIC_INCH,
+         &KILO_LITRE, &MEGA_LITRE, &GIGA_LITRE,
+         &METRIC_CUP, &METRIC_TABLESPOON, &METRIC_TEASPOON, &IMP_CUBIC_INCH,
          &IMP_FL_OUNCE, &IMP_PINT, &IMP_QUART, &IMP_GALLON,
          &US_FL_OUNCE, &US_PINT, &US_QUART, &US_GALLON,
+         &US_CUP, &US_TABLESPOON, &US_TEASPOON,
+         &US_DRY_PINT, &US_DRY_QUART, &US_DRY_GALLON, &US_PECK, &US_BUSHEL,
     ]
 }
 
@@ -194,6 +360,8 @@ mod tests {
     use crate::conversions::volume::{CU_METRE, CUBIC_CENTIMETRE, CUBIC_KILOMETRE, GIGA_LITRE, KILO_LITRE, LITRE, MEGA_LITRE};
     use crate::conversions::volume::{IMP_CUBIC_INCH, IMP_FL_OUNCE, IMP_GALLON, IMP_PINT, IMP_QUART};
     use crate::conversions::volume::{US_FL_OUNCE, US_GALLON, US_PINT, US_QUART};
+    use crate::conversions::volume::{METRIC_CUP, METRIC_TABLESPOON, METRIC_TEASPOON, US_CUP, US_TABLESPOON, US_TEASPOON};
+    use crate::conversions::volume::{US_BUSHEL, US_DRY_GALLON, US_DRY_PINT, US_DRY_QUART, US_PECK};
 
     #[test]
     fn test_all_metric_volumes() {
@@ -210,6 +378,7 @@ mod tests {
     fn test_all_imperial_volumes() {
         assert_eq!(convert(&15.5, &IMP_PINT, &IMP_GALLON), 1.9375);
         assert_near!(convert(&20.0, &IMP_FL_OUNCE, &IMP_CUBIC_INCH), 34.6774291);
+        assert_near!(convert(&34.6774291, &IMP_CUBIC_INCH, &IMP_FL_OUNCE), 20.0);
         assert_near!(convert(&1.0, &IMP_PINT, &IMP_FL_OUNCE), 20.0);
         assert_near!(convert(&1.0, &IMP_QUART, &IMP_FL_OUNCE), 40.0);
         assert_near!(convert(&1.0, &IMP_GALLON, &IMP_FL_OUNCE), 160.0);
@@ -224,6 +393,36 @@ mod tests {
         assert_near!(convert(&10.0, &US_PINT, &US_GALLON), 1.25);
     }
 
+    #[test]
+    fn test_all_us_dry_volumes() {
+        assert_near!(convert(&1.0, &US_DRY_QUART, &US_DRY_PINT), 2.0);
+        assert_near!(convert(&1.0, &US_DRY_GALLON, &US_DRY_QUART), 4.0);
+        assert_near!(convert(&1.0, &US_PECK, &US_DRY_GALLON), 2.0);
+        assert_near!(convert(&1.0, &US_BUSHEL, &US_PECK), 4.0);
+        assert_near!(convert(&1.0, &US_DRY_PINT, &US_PINT), 1.1636471861);
+    }
+
+    #[test]
+    fn test_us_dry_to_metric_volumes() {
+        assert_near!(convert(&1.0, &US_DRY_PINT, &LITRE), 0.5506104713575);
+        assert_near!(convert(&1.0, &US_DRY_QUART, &LITRE), 1.101220942715);
+        assert_near!(convert(&1.0, &US_DRY_GALLON, &LITRE), 4.40488377086);
+        assert_near!(convert(&1.0, &US_PECK, &LITRE), 8.80976754172);
+        assert_near!(convert(&1.0, &US_BUSHEL, &LITRE), 35.23907016688);
+        assert_near!(convert(&1.0, &LITRE, &US_BUSHEL), 0.0283775932);
+    }
+
+    #[test]
+    fn test_cooking_volumes() {
+        assert_near!(convert(&1.0, &US_CUP, &CUBIC_CENTIMETRE), 236.5882365);
+        assert_eq!(convert(&3.0, &US_TEASPOON, &US_TABLESPOON), 1.0);
+        assert_eq!(convert(&1.0, &US_CUP, &US_TABLESPOON), 16.0);
+        assert_near!(convert(&1.0, &US_CUP, &US_FL_OUNCE), 8.0);
+        assert_near!(convert(&1.0, &METRIC_CUP, &CUBIC_CENTIMETRE), 250.0);
+        assert_near!(convert(&1.0, &METRIC_TABLESPOON, &METRIC_TEASPOON), 3.0);
+        assert_near!(convert(&1.0, &METRIC_TEASPOON, &US_TEASPOON), 1.0144207);
+    }
+
     #[test]
     fn test_metric_to_imperial_volumes() {
         assert_near!(convert(&1.0, &LITRE, &IMP_PINT), 1.759753986);
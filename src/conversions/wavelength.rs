@@ -0,0 +1,53 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use crate::evaluator::constants::C;
+
+// Frequency and wavelength are different dimensions, and the relationship λ = c/f isn't a
+// linear scaling, so they are related here rather than through a Unit.
+
+/// The wavelength in metres of a wave with frequency `hz` in hertz.
+/// Frequencies that aren't positive have no wavelength.
+pub(crate) fn freq_to_wavelength(hz: f64) -> f64 {
+    if hz > 0.0 { C.value / hz } else { f64::NAN }
+}
+
+/// The frequency in hertz of a wave with wavelength `m` in metres.
+/// Wavelengths that aren't positive have no frequency.
+pub(crate) fn wavelength_to_freq(m: f64) -> f64 {
+    if m > 0.0 { C.value / m } else { f64::NAN }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_near;
+    use crate::conversions::wavelength::*;
+
+    #[test]
+    fn test_wavelength() {
+        assert_near!(freq_to_wavelength(100e6), 2.99792458);
+        assert_near!(wavelength_to_freq(2.99792458), 100e6);
+        assert_near!(wavelength_to_freq(freq_to_wavelength(2.4e9)), 2.4e9);
+        assert!(freq_to_wavelength(0.0).is_nan());
+        assert!(wavelength_to_freq(-1.0).is_nan());
+    }
+}
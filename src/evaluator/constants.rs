@@ -21,27 +21,36 @@
  */
 #![allow(non_upper_case_globals)]
 
+use std::borrow::Cow;
 use std::fmt::Debug;
 
 use crate::evaluator::Token;
+use crate::evaluator::user_constants;
 
+/// A named value. The built in constants borrow their names, the user's own constants own them.
 #[derive(Clone, Debug)]
 pub(crate) struct Constant {
-    pub name: &'static str,
-    pub long_name: &'static str,
+    pub name: Cow<'static, str>,
+    pub long_name: Cow<'static, str>,
     pub value: f64,
+    /// The units the value is measured in, empty for pure numbers like π
+    pub units: &'static str,
 }
 
 impl Constant {
 
-    pub(crate) fn name(&self) -> &'static str {
+    pub(crate) fn name(&self) -> &str {
         &self.name
     }
 
-    pub(crate) fn long_name(&self) -> &'static str {
+    pub(crate) fn long_name(&self) -> &str {
         &self.long_name
     }
 
+    pub(crate) fn units(&self) -> &'static str {
+        &self.units
+    }
+
     pub(crate) fn is_token(
         &self,
         chars: &Vec<char>,
@@ -52,12 +61,14 @@ impl Constant {
         let name = self.name();
         let cons_as_chars = name.chars().collect::<Vec<char>>();
         let name_len = cons_as_chars.len();
-        let expr_chars_to_compare = &chars[*i..*i + name_len];
-        if i + name_len - 1 < *exp_len  && expr_chars_to_compare == cons_as_chars
+        if i + name_len - 1 < *exp_len  && chars[*i..*i + name_len] == cons_as_chars
         {
             //Need to check the next character is not alphanumeric, otherwise it is the name of a different function
             if (i + name_len < *exp_len) && chars[*i + name_len].is_alphanumeric() {
                 None
+            } else if *i > 0 && chars[*i - 1].is_alphanumeric() {
+                // Nor the previous character, so `C` isn't found at the end of a longer name
+                None
             } else {
                 Some((Token::Number(self.value), name_len))
             }
@@ -68,29 +79,78 @@ impl Constant {
 
 }
 
-pub static Pi: Constant =  Constant{name: "π", long_name: "PI", value: std::f64::consts::PI};
-pub static Euler: Constant =  Constant{name: "ℇ", long_name: "Euler's Const", value: std::f64::consts::E};
-pub static Phi: Constant =  Constant{name: "ɸ", long_name: "Golden Ratio", value: 1.618};
-pub static C: Constant =  Constant{name: "C", long_name: "Speed of Light", value: 299792458.0};
-pub static Planck: Constant =  Constant{name: "ℎ", long_name: "Planks Const", value: 6.626e-34};
-pub static G: Constant =  Constant{name: "G", long_name: "Grav Const", value: 6.674e-11};
+pub static Pi: Constant =  Constant{name: Cow::Borrowed("π"), long_name: Cow::Borrowed("PI"), value: std::f64::consts::PI, units: ""};
+pub static Euler: Constant =  Constant{name: Cow::Borrowed("ℇ"), long_name: Cow::Borrowed("Euler's Const"), value: std::f64::consts::E, units: ""};
+pub static Phi: Constant =  Constant{name: Cow::Borrowed("ɸ"), long_name: Cow::Borrowed("Golden Ratio"), value: 1.618033988749895, units: ""}; // (1 + √5) / 2
+pub static C: Constant =  Constant{name: Cow::Borrowed("C"), long_name: Cow::Borrowed("Speed of Light"), value: 299792458.0, units: "m/s"};
+pub static Planck: Constant =  Constant{name: Cow::Borrowed("ℎ"), long_name: Cow::Borrowed("Planks Const"), value: 6.626e-34, units: "J·s"};
+pub static G: Constant =  Constant{name: Cow::Borrowed("G"), long_name: Cow::Borrowed("Grav Const"), value: 6.674e-11, units: "m³/(kg·s²)"};
+pub static Avogadro: Constant =  Constant{name: Cow::Borrowed("Nₐ"), long_name: Cow::Borrowed("Avogadro's Number"), value: 6.02214076e23, units: "mol⁻¹"};
+pub static Boltzmann: Constant =  Constant{name: Cow::Borrowed("kʙ"), long_name: Cow::Borrowed("Boltzmann Const"), value: 1.380649e-23, units: "J/K"};
+pub static Charge: Constant =  Constant{name: Cow::Borrowed("qₑ"), long_name: Cow::Borrowed("Elementary Charge"), value: 1.602176634e-19, units: "C"};
+
+/// The physical constants, which are all measured in some units.
+pub(crate) fn get_physical() -> Vec<&'static Constant> {
+    vec![&C, &Planck, &G, &Avogadro, &Boltzmann, &Charge]
+}
+
+/// The constants that are always available.
+pub(crate) fn get_builtin() -> Vec<&'static Constant> {
+    vec![&Pi, &Euler, &Phi, &C, &Planck, &G, &Avogadro, &Boltzmann, &Charge]
+}
 
-pub(crate) fn get_all() -> Vec<&'static Constant> {
-    vec![&Pi, &Euler, &Phi, &C, &Planck, &G]
+/// The built in constants followed by the user's own.
+pub(crate) fn get_all() -> Vec<Constant> {
+    get_builtin().into_iter()
+        .cloned()
+        .chain(user_constants::manager().constants())
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::borrow::Cow;
+
     use crate::assert_near;
-    use crate::evaluator::constants::Constant;
+    use crate::evaluator::constants::{C, Constant, get_all, get_physical, Phi};
 
     #[test]
     fn test_const() {
         let f = Constant {
-            name: "π",
-            long_name: "Pi",
+            name: Cow::Borrowed("π"),
+            long_name: Cow::Borrowed("Pi"),
             value: 50.2,
+            units: "",
         };
         assert_near!(f.value, 50.2);
     }
+
+    #[test]
+    fn test_is_token() {
+        let chars = |s: &str| s.chars().collect::<Vec<char>>();
+        assert!(C.is_token(&chars("C"), &1, &0).is_some());
+        assert!(C.is_token(&chars("2*C"), &3, &2).is_some());
+        assert!(C.is_token(&chars("Cat"), &3, &0).is_none());
+        assert!(C.is_token(&chars("aC"), &2, &1).is_none());
+        assert!(C.is_token(&chars("2C"), &2, &1).is_none());
+        assert!(C.is_token(&chars("2+"), &2, &1).is_none());
+    }
+
+    #[test]
+    fn test_golden_ratio() {
+        assert_near!(Phi.value, (1.0 + 5f64.sqrt()) / 2.0, 1e-15);
+        assert_near!(Phi.value * Phi.value, Phi.value + 1.0, 1e-12);
+    }
+
+    #[test]
+    fn test_units() {
+        for c in get_physical() {
+            assert!(!c.units().is_empty(), "{} has no units", c.long_name());
+        }
+        for c in get_all() {
+            if !get_physical().iter().any(|p| p.name() == c.name()) {
+                assert!(c.units().is_empty(), "{} is not a pure number", c.long_name());
+            }
+        }
+    }
 }
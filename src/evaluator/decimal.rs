@@ -0,0 +1,157 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use std::str::FromStr;
+
+use log::warn;
+use rust_decimal::prelude::{Decimal, FromPrimitive, ToPrimitive};
+
+use crate::evaluator::{AngleMode, AstNode, Token};
+
+/// Decimal arithmetic for when binary floating point error is not acceptable, e.g. money.
+/// Addition, subtraction, multiplication, division and whole number powers are exact.
+/// Functions and fractional powers fall back to floating point, and the result is flagged as
+/// approximate.
+impl AstNode {
+    /// Evaluate with decimal arithmetic, returning the value and whether any part of it fell back to
+    /// floating point.
+    pub(crate) fn evaluate_decimal(&self, mode: &AngleMode) -> Result<(Decimal, bool), String> {
+        let mut approximate = false;
+        let value = self.decimal(mode, &mut approximate)?;
+        Ok((value, approximate))
+    }
+
+    fn decimal(&self, mode: &AngleMode, approximate: &mut bool) -> Result<Decimal, String> {
+        match self {
+            // Numbers are displayed in their shortest form, which is what was typed for a literal
+            AstNode::Number(value) => Decimal::from_str(&value.to_string())
+                .or_else(|_| to_decimal(*value)),
+            AstNode::Quantity(_, _) => Err(String::from("Units are not supported in decimal mode")),
+            AstNode::UnaryOp { op, expr } => {
+                let value = expr.decimal(mode, approximate)?;
+                match op {
+                    Token::Minus => Ok(-value),
+                    Token::Percent => value.checked_div(Decimal::ONE_HUNDRED).ok_or_else(overflow),
+                    Token::UnaryFunction(f) => {
+                        warn!("{} is not exact in decimal mode, using floating point", f.name());
+                        *approximate = true;
+                        to_decimal(f.evaluate(to_f64(value)?, mode)?)
+                    }
+                    _ => panic!("Unexpected operator in unary operation: {:?}", op),
+                }
+            }
+            AstNode::BinaryOp { left, op, right } => {
+                let left = left.decimal(mode, approximate)?;
+                let right = right.decimal(mode, approximate)?;
+                match op {
+                    Token::Plus => left.checked_add(right).ok_or_else(overflow),
+                    Token::Minus => left.checked_sub(right).ok_or_else(overflow),
+                    Token::Multiply => left.checked_mul(right).ok_or_else(overflow),
+                    Token::Divide => {
+                        if right.is_zero() {
+                            Err(String::from("Division by zero"))
+                        } else {
+                            left.checked_div(right).ok_or_else(overflow)
+                        }
+                    }
+                    Token::FloorDivide => {
+                        if right.is_zero() {
+                            Err(String::from("Division by zero"))
+                        } else {
+                            left.checked_div(right).map(|v| v.floor()).ok_or_else(overflow)
+                        }
+                    }
+                    Token::Exponent => power(left, right, approximate),
+                    _ => panic!("Unexpected operator in binary operation: {:?}", op),
+                }
+            }
+            AstNode::Function { func, expr } => {
+                let value = expr.decimal(mode, approximate)?;
+                warn!("{} is not exact in decimal mode, using floating point", func.name());
+                *approximate = true;
+                to_decimal(func.evaluate(to_f64(value)?, mode)?)
+            }
+            AstNode::PercentChange { left, op, percent } => {
+                let left = left.decimal(mode, approximate)?;
+                let change = left.checked_mul(percent.decimal(mode, approximate)?).ok_or_else(overflow)?;
+                match op {
+                    Token::Plus => left.checked_add(change).ok_or_else(overflow),
+                    Token::Minus => left.checked_sub(change).ok_or_else(overflow),
+                    _ => panic!("Unexpected operator in percentage change: {:?}", op),
+                }
+            }
+            AstNode::MultiFunction { func, args } => {
+                let values = args.iter()
+                    .map(|arg| to_f64(arg.decimal(mode, approximate)?))
+                    .collect::<Result<Vec<f64>, String>>()?;
+                warn!("{} is not exact in decimal mode, using floating point", func.name());
+                *approximate = true;
+                to_decimal(func.evaluate(&values, mode)?)
+            }
+        }
+    }
+}
+
+/// Raise to a power, exactly for whole number exponents, by repeated squaring.
+fn power(base: Decimal, exponent: Decimal, approximate: &mut bool) -> Result<Decimal, String> {
+    match exponent.to_i64() {
+        Some(n) if exponent.fract().is_zero() => {
+            let mut result = Decimal::ONE;
+            let mut square = base;
+            let mut remaining = n.unsigned_abs();
+            while remaining > 0 {
+                if remaining & 1 == 1 {
+                    result = result.checked_mul(square).ok_or_else(overflow)?;
+                }
+                remaining >>= 1;
+                if remaining > 0 {
+                    square = square.checked_mul(square).ok_or_else(overflow)?;
+                }
+            }
+            if n < 0 {
+                if result.is_zero() {
+                    return Err(String::from("Division by zero"));
+                }
+                Decimal::ONE.checked_div(result).ok_or_else(overflow)
+            } else {
+                Ok(result)
+            }
+        }
+        _ => {
+            warn!("Fractional powers are not exact in decimal mode, using floating point");
+            *approximate = true;
+            to_decimal(to_f64(base)?.powf(to_f64(exponent)?))
+        }
+    }
+}
+
+fn to_f64(value: Decimal) -> Result<f64, String> {
+    value.to_f64().ok_or_else(|| format!("{} cannot be converted to floating point", value))
+}
+
+fn to_decimal(value: f64) -> Result<Decimal, String> {
+    Decimal::from_f64(value).ok_or_else(|| format!("{} cannot be represented as a decimal", value))
+}
+
+fn overflow() -> String {
+    String::from("The result is too large for decimal mode")
+}
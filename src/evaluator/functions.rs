@@ -20,9 +20,17 @@
  *
  */
 
+use std::f64::consts::PI;
 use std::fmt::Debug;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::LazyLock;
+use std::time::{SystemTime, UNIX_EPOCH};
 
+use crate::conversions::great_circle::great_circle_distance;
+use crate::conversions::length::KILOMETRE;
+use crate::conversions::Unit;
+use crate::conversions::wavelength::{freq_to_wavelength, wavelength_to_freq};
 use crate::evaluator::{AngleMode, Token};
 
 #[derive(Clone, Debug)]
@@ -32,8 +40,14 @@ pub(crate) struct Function {
 }
 
 impl Function {
-    pub(crate) fn evaluate(&self, val: f64, mode: &AngleMode) -> f64 {
-        (self.function)(val, mode)
+    /// Evaluate the function, with an error if the value is outside its domain, e.g. `sqrt(-1)`.
+    pub(crate) fn evaluate(&self, val: f64, mode: &AngleMode) -> Result<f64, String> {
+        let result = (self.function)(val, mode);
+        if result.is_nan() && !val.is_nan() {
+            Err(domain_error(self.name, val))
+        } else {
+            Ok(result)
+        }
     }
 
     pub(crate) fn name(&self) -> &'static str {
@@ -46,21 +60,84 @@ impl Function {
         exp_len: &usize,
         i: &usize,
     ) -> Option<(Token, usize)> {
-        let name = self.name();
-        if i + name.len() - 1 < *exp_len
-            && chars[*i..*i + name.len()] == name.chars().collect::<Vec<char>>()
-        {
-            //Need to check the next character is not alphanumeric, otherwise it is the name of a different function
-            if (i + name.len() < *exp_len) && chars[*i + name.len()].is_alphanumeric() {
-                None
+        match_name(self.name(), chars, exp_len, i)
+            .map(|len| (Token::UnaryFunction(Rc::new(self.clone())), len))
+    }
+
+}
+
+/// A function of several arguments separated by commas, e.g. `ncr(5, 2)`.
+#[derive(Clone, Debug)]
+pub(crate) struct MultiFunction {
+    name: &'static str,
+    /// The least and the most number of arguments
+    arity: (usize, usize),
+    function: fn(&[f64], &AngleMode) -> Result<f64, String>,
+    /// The unit of the result, if it is a quantity rather than a plain number
+    unit: Option<&'static Unit>,
+}
+
+impl MultiFunction {
+    pub(crate) fn evaluate(&self, args: &[f64], mode: &AngleMode) -> Result<f64, String> {
+        let (least, most) = self.arity;
+        if args.len() < least || args.len() > most {
+            return Err(if least == most {
+                format!("{} takes {} arguments", self.name, least)
+            } else if most == usize::MAX {
+                format!("{} takes at least {} arguments", self.name, least)
             } else {
-                Some((Token::UnaryFunction(Rc::new(self.clone())), name.len()))
-            }
-        } else {
-            None
+                format!("{} takes {} to {} arguments", self.name, least, most)
+            });
         }
+        (self.function)(args, mode)
+    }
+
+    pub(crate) fn name(&self) -> &'static str {
+        &self.name
+    }
+
+    pub(crate) fn unit(&self) -> Option<&'static Unit> {
+        self.unit
     }
 
+    pub(crate) fn is_token(
+        &self,
+        chars: &Vec<char>,
+        exp_len: &usize,
+        i: &usize,
+    ) -> Option<(Token, usize)> {
+        match_name(self.name(), chars, exp_len, i)
+            .map(|len| (Token::MultiFunction(Rc::new(self.clone())), len))
+    }
+}
+
+/// Explain why a function has no value for `v`.
+fn domain_error(name: &str, v: f64) -> String {
+    match name {
+        "sqrt" | "ln" | "log" | "log2" => format!("{} of negative number", name),
+        "asin" | "acos" | "atanh" => format!("{} requires -1 <= x <= 1", name),
+        "acosec" | "asec" => format!("{} requires x <= -1 or x >= 1", name),
+        "acosh" => format!("{} requires x >= 1", name),
+        "factorial" => format!("{} requires a positive whole number", name),
+        "gamma" | "lgamma" => format!("{} is undefined for 0 and negative whole numbers", name),
+        "wavelength" | "frequency" => format!("{} requires a positive value", name),
+        _ => format!("{} is undefined for {}", name, v),
+    }
+}
+
+/// The length of the function name if it is at position `i`.
+fn match_name(name: &str, chars: &Vec<char>, exp_len: &usize, i: &usize) -> Option<usize> {
+    let len = name.chars().count();
+    if i + len - 1 < *exp_len && chars[*i..*i + len] == name.chars().collect::<Vec<char>>() {
+        //Need to check the next character is not alphanumeric, otherwise it is the name of a different function
+        if (i + len < *exp_len) && chars[*i + len].is_alphanumeric() {
+            None
+        } else {
+            Some(len)
+        }
+    } else {
+        None
+    }
 }
 
 fn do_trig(v: f64, mode: &AngleMode, f: fn(f64) -> f64) -> f64 {
@@ -81,6 +158,54 @@ fn do_atrig(v: f64, mode: &AngleMode, f: fn(f64) -> f64) -> f64 {
     }
 }
 
+/// Coefficients of the Lanczos approximation to the gamma function, with g = 7
+static LANCZOS_G: f64 = 7.0;
+static LANCZOS: [f64; 9] = [
+    0.999_999_999_999_809_93,
+    676.520_368_121_885_1,
+    -1_259.139_216_722_402_8,
+    771.323_428_777_653_13,
+    -176.615_029_162_140_59,
+    12.507_343_278_686_905,
+    -0.138_571_095_265_720_12,
+    9.984_369_578_019_571_6e-6,
+    1.505_632_735_149_311_6e-7,
+];
+
+/// The Lanczos sum and the shifted argument `t` for `x >= 0.5`
+fn lanczos(x: f64) -> (f64, f64) {
+    let x = x - 1.0;
+    let sum = LANCZOS[1..].iter().enumerate()
+        .fold(LANCZOS[0], |sum, (i, c)| sum + c / (x + i as f64 + 1.0));
+    (sum, x + LANCZOS_G + 0.5)
+}
+
+/// The gamma function, which extends the factorial so that gamma(n) = (n - 1)!
+/// Values below 0.5 use the reflection formula. Gamma has poles at zero and the negative integers.
+fn gamma(x: f64) -> f64 {
+    if x <= 0.0 && x.fract() == 0.0 {
+        f64::NAN
+    } else if x < 0.5 {
+        PI / ((PI * x).sin() * gamma(1.0 - x))
+    } else {
+        let (sum, t) = lanczos(x);
+        (2.0 * PI).sqrt() * t.powf(x - 0.5) * (-t).exp() * sum
+    }
+}
+
+/// The natural log of the absolute value of the gamma function. This stays finite for arguments
+/// where gamma itself overflows.
+fn ln_gamma(x: f64) -> f64 {
+    if x <= 0.0 && x.fract() == 0.0 {
+        f64::INFINITY
+    } else if x < 0.5 {
+        (PI / (PI * x).sin().abs()).ln() - ln_gamma(1.0 - x)
+    } else {
+        let (sum, t) = lanczos(x);
+        0.5 * (2.0 * PI).ln() + (x - 0.5) * t.ln() - t + sum.ln()
+    }
+}
+
 pub(crate) fn get_all() -> Vec<Function> {
     vec![
         Function {
@@ -192,7 +317,7 @@ pub(crate) fn get_all() -> Vec<Function> {
             function: |v, _| -> f64 {
                 if v > 170.0 {
                     f64::INFINITY
-                } else if v.fract() != 0.0 {
+                } else if v.fract() != 0.0 || v < 0.0 {
                     f64::NAN
                 } else {
                     let mut factorial: f64 = 1.0;
@@ -205,6 +330,214 @@ pub(crate) fn get_all() -> Vec<Function> {
                 }
             },
         },
+        // Angle conversions ignore the angle mode
+        Function {
+            name: "torad",
+            function: |v, _| v.to_radians(),
+        },
+        Function {
+            name: "todeg",
+            function: |v, _| v.to_degrees(),
+        },
+        Function {
+            name: "gamma",
+            function: |v, _| gamma(v),
+        },
+        Function {
+            name: "lgamma",
+            function: |v, _| ln_gamma(v),
+        },
+        // Electromagnetic waves, in hertz and metres
+        Function {
+            name: "wavelength",
+            function: |v, _| freq_to_wavelength(v),
+        },
+        Function {
+            name: "frequency",
+            function: |v, _| wavelength_to_freq(v),
+        },
+    ]
+}
+
+/// The largest whole number that counting up in floating point can get past, 2^53.
+static MAX_EXACT_INTEGER: f64 = 9_007_199_254_740_992.0;
+
+/// Check the arguments of combinations and permutations are whole numbers with 0 <= r <= n.
+/// Beyond 2^53 adding one no longer changes a number, so n can't be larger than that.
+fn choose_args(name: &str, args: &[f64]) -> Result<(f64, f64), String> {
+    let (n, r) = (args[0], args[1]);
+    if n.fract() != 0.0 || r.fract() != 0.0 {
+        Err(format!("{} requires whole numbers", name))
+    } else if n > MAX_EXACT_INTEGER {
+        Err(format!("{} requires n <= 2^53", name))
+    } else if r < 0.0 || r > n {
+        Err(format!("{} requires 0 <= r <= n", name))
+    } else {
+        Ok((n, r))
+    }
+}
+
+/// The number of ways to choose r of n things, n!/(r!(n-r)!). The product is built up a term at
+/// a time so it stays exact for as long as possible rather than overflowing in the factorials.
+/// Once it overflows there is no point going on, which also keeps a large r from taking forever.
+fn combinations(n: f64, r: f64) -> f64 {
+    let r = r.min(n - r);
+    let mut result: f64 = 1.0;
+    let mut i = 1.0;
+    while i <= r && result.is_finite() {
+        result = result * (n - r + i) / i;
+        i += 1.0;
+    }
+    result.round()
+}
+
+/// The number of ordered arrangements of r of n things, n!/(n-r)!.
+fn permutations(n: f64, r: f64) -> f64 {
+    let mut result: f64 = 1.0;
+    let mut i = n - r + 1.0;
+    while i <= n && result.is_finite() {
+        result *= i;
+        i += 1.0;
+    }
+    result
+}
+
+/// Convert the arguments of an integer function, rejecting anything that is not a whole number.
+fn whole_numbers(name: &str, args: &[f64]) -> Result<Vec<i64>, String> {
+    args.iter()
+        .map(|v| if v.fract() == 0.0 && v.abs() < i64::MAX as f64 {
+            Ok(*v as i64)
+        } else {
+            Err(format!("{} requires whole numbers", name))
+        })
+        .collect()
+}
+
+/// The greatest common divisor by Euclid's algorithm. It is always positive, and gcd(0, n) is n.
+fn gcd(a: i64, b: i64) -> i64 {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// The least common multiple, which is 0 if either number is 0.
+fn lcm(a: i64, b: i64) -> Result<i64, String> {
+    if a == 0 || b == 0 {
+        return Ok(0);
+    }
+    (a / gcd(a, b)).checked_mul(b).map(i64::abs).ok_or_else(|| String::from("lcm is too large"))
+}
+
+/// The state of the random number generator, seeded from the clock.
+static RANDOM_STATE: LazyLock<AtomicU64> = LazyLock::new(|| {
+    AtomicU64::new(SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_nanos() as u64))
+});
+static GOLDEN_GAMMA: u64 = 0x9E37_79B9_7F4A_7C15;
+
+/// A uniformly distributed random number in [0, 1), from the SplitMix64 generator. It is quick
+/// and good enough for simulations, but not for anything needing security.
+fn random() -> f64 {
+    let mut z = RANDOM_STATE.fetch_add(GOLDEN_GAMMA, Ordering::Relaxed).wrapping_add(GOLDEN_GAMMA);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^= z >> 31;
+    // The top 53 bits fill the mantissa
+    (z >> 11) as f64 / (1u64 << 53) as f64
+}
+
+pub(crate) fn get_all_multi() -> Vec<MultiFunction> {
+    vec![
+        MultiFunction {
+            name: "ncr",
+            arity: (2, 2),
+            function: |args, _| choose_args("ncr", args).map(|(n, r)| combinations(n, r)),
+            unit: None,
+        },
+        MultiFunction {
+            name: "npr",
+            arity: (2, 2),
+            function: |args, _| choose_args("npr", args).map(|(n, r)| permutations(n, r)),
+            unit: None,
+        },
+        MultiFunction {
+            name: "gcd",
+            arity: (2, 2),
+            function: |args, _| whole_numbers("gcd", args).map(|n| gcd(n[0], n[1]) as f64),
+            unit: None,
+        },
+        MultiFunction {
+            name: "lcm",
+            arity: (2, 2),
+            function: |args, _| {
+                let n = whole_numbers("lcm", args)?;
+                lcm(n[0], n[1]).map(|v| v as f64)
+            },
+            unit: None,
+        },
+        MultiFunction {
+            name: "rand",
+            arity: (0, 2),
+            function: |args, _| match args {
+                [] => Ok(random()),
+                [a, b] => Ok(a + (b - a) * random()),
+                _ => Err(String::from("rand takes no arguments, or a range")),
+            },
+            unit: None,
+        },
+        MultiFunction {
+            name: "hypot",
+            arity: (2, 2),
+            function: |args, _| Ok(args[0].hypot(args[1])),
+            unit: None,
+        },
+        MultiFunction {
+            name: "min",
+            arity: (2, usize::MAX),
+            function: |args, _| Ok(args.iter().copied().fold(f64::INFINITY, f64::min)),
+            unit: None,
+        },
+        MultiFunction {
+            name: "max",
+            arity: (2, usize::MAX),
+            function: |args, _| Ok(args.iter().copied().fold(f64::NEG_INFINITY, f64::max)),
+            unit: None,
+        },
+        MultiFunction {
+            name: "clamp",
+            arity: (3, 3),
+            function: |args, _| {
+                let (value, lo, hi) = (args[0], args[1], args[2]);
+                if lo > hi {
+                    Err(String::from("clamp requires lo <= hi"))
+                } else {
+                    Ok(value.clamp(lo, hi))
+                }
+            },
+            unit: None,
+        },
+        // A tip, or a tax, of a percentage of an amount, and the amount with it added
+        MultiFunction {
+            name: "tip",
+            arity: (2, 2),
+            function: |args, _| Ok(args[0] * args[1] / 100.0),
+            unit: None,
+        },
+        MultiFunction {
+            name: "total_with_tip",
+            arity: (2, 2),
+            function: |args, _| Ok(args[0] * (1.0 + args[1] / 100.0)),
+            unit: None,
+        },
+        // The distance between two latitude, longitude pairs in degrees, as a length in kilometres
+        // that can be converted, e.g. `haversine(51.5, -0.13, 48.86, 2.35) in mi`
+        MultiFunction {
+            name: "haversine",
+            arity: (4, 4),
+            function: |args, _| great_circle_distance(args[0], args[1], args[2], args[3], &KILOMETRE),
+            unit: Some(&KILOMETRE),
+        },
     ]
 }
 
@@ -212,7 +545,7 @@ pub(crate) fn get_all() -> Vec<Function> {
 mod tests {
     use crate::assert_near;
     use crate::evaluator::AngleMode;
-    use crate::evaluator::functions::Function;
+    use crate::evaluator::functions::{choose_args, combinations, Function, gamma, gcd, get_all, lcm, ln_gamma, permutations, random};
 
     #[test]
     fn test_fn() {
@@ -220,6 +553,75 @@ mod tests {
             name: "sin",
             function: |v, _| v.sin(),
         };
-        assert_near!(f.evaluate(std::f64::consts::PI / 2.0, &AngleMode::Radians), 1.0);
+        assert_near!(f.evaluate(std::f64::consts::PI / 2.0, &AngleMode::Radians).unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_domain_errors() {
+        let find = |name: &str| get_all().into_iter().find(|f| f.name() == name).unwrap();
+        assert_eq!(find("sqrt").evaluate(-1.0, &AngleMode::Radians), Err("sqrt of negative number".to_string()));
+        assert_eq!(find("ln").evaluate(-1.0, &AngleMode::Radians), Err("ln of negative number".to_string()));
+        assert_eq!(find("acos").evaluate(2.0, &AngleMode::Degrees), Err("acos requires -1 <= x <= 1".to_string()));
+        assert_eq!(find("sqrt").evaluate(f64::NAN, &AngleMode::Radians).map(f64::is_nan), Ok(true));
+        assert_eq!(find("ln").evaluate(0.0, &AngleMode::Radians), Ok(f64::NEG_INFINITY));
+    }
+
+    #[test]
+    fn test_gamma() {
+        assert_near!(gamma(5.0), 24.0, 1e-9);
+        assert_near!(gamma(1.0), 1.0, 1e-12);
+        assert_near!(gamma(0.5), std::f64::consts::PI.sqrt(), 1e-12);
+        assert_near!(gamma(-0.5), -2.0 * std::f64::consts::PI.sqrt(), 1e-12);
+        assert!(gamma(0.0).is_nan());
+        assert!(gamma(-2.0).is_nan());
+    }
+
+    #[test]
+    fn test_ln_gamma() {
+        assert_near!(ln_gamma(5.0), 24f64.ln(), 1e-12);
+        assert_near!(ln_gamma(0.5), std::f64::consts::PI.sqrt().ln(), 1e-12);
+        assert_near!(ln_gamma(-0.5), (2.0 * std::f64::consts::PI.sqrt()).ln(), 1e-12);
+        // gamma(200) overflows, but its log does not
+        assert_near!(ln_gamma(200.0), 857.933_669_825_857_5, 1e-9);
+    }
+
+    #[test]
+    fn test_combinations() {
+        assert_near!(combinations(5.0, 2.0), 10.0);
+        assert_near!(combinations(5.0, 0.0), 1.0);
+        assert_near!(combinations(52.0, 5.0), 2598960.0);
+        // 1000! overflows, but this doesn't
+        assert_near!(combinations(1000.0, 2.0), 499500.0);
+        assert_near!(permutations(5.0, 2.0), 20.0);
+        assert_near!(permutations(5.0, 5.0), 120.0);
+        assert_near!(permutations(5.0, 0.0), 1.0);
+    }
+
+    #[test]
+    fn test_huge_combinations() {
+        let start = std::time::Instant::now();
+        assert_eq!(combinations(1e12, 5e11), f64::INFINITY);
+        assert_eq!(permutations(1e12, 5e11), f64::INFINITY);
+        assert!(start.elapsed() < std::time::Duration::from_secs(1));
+        assert_eq!(choose_args("ncr", &[1e17, 1e16]), Err("ncr requires n <= 2^53".to_string()));
+        assert_eq!(choose_args("npr", &[2f64.powi(53), 1.0]), Ok((2f64.powi(53), 1.0)));
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 5), 5);
+        assert_eq!(gcd(-4, 6), 2);
+        assert_eq!(lcm(4, 6), Ok(12));
+        assert_eq!(lcm(0, 6), Ok(0));
+        assert!(lcm(i64::MAX, i64::MAX - 1).is_err());
+    }
+
+    #[test]
+    fn test_random() {
+        let samples: Vec<f64> = (0..1000).map(|_| random()).collect();
+        assert!(samples.iter().all(|v| (0.0..1.0).contains(v)));
+        // Not all the same
+        assert!(samples.iter().any(|v| *v != samples[0]));
     }
 }
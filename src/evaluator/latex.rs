@@ -0,0 +1,102 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use crate::evaluator::{AstNode, Token};
+
+/// Write an expression as LaTeX, e.g. for pasting into a document.
+/// Parentheses are added where needed from the precedence of the operators.
+impl AstNode {
+    pub(crate) fn to_latex(&self) -> String {
+        match self {
+            AstNode::Number(value) => value.to_string(),
+            AstNode::Quantity(value, unit) => format!("{}\\,\\mathrm{{{}}}", value, unit.symbol),
+            AstNode::UnaryOp { op, expr } => match op {
+                Token::Minus => format!("-{}", expr.latex_operand(1)),
+                Token::Percent => format!("{}\\%", expr.latex_operand(3)),
+                Token::UnaryFunction(f) => function_latex(f.name(), &expr.to_latex()),
+                _ => panic!("Unexpected operator in unary operation: {:?}", op),
+            },
+            AstNode::BinaryOp { left, op, right } => match op {
+                Token::Plus => format!("{} + {}", left.to_latex(), right.to_latex()),
+                Token::Minus => format!("{} - {}", left.to_latex(), right.latex_operand(1)),
+                Token::Multiply => format!("{} \\times {}", left.latex_operand(1), right.latex_operand(1)),
+                Token::Divide => format!("\\frac{{{}}}{{{}}}", left.to_latex(), right.to_latex()),
+                Token::FloorDivide => format!("\\left\\lfloor\\frac{{{}}}{{{}}}\\right\\rfloor", left.to_latex(), right.to_latex()),
+                Token::Exponent => format!("{}^{{{}}}", left.latex_operand(2), right.to_latex()),
+                _ => panic!("Unexpected operator in binary operation: {:?}", op),
+            },
+            AstNode::Function { func, expr } => function_latex(func.name(), &expr.to_latex()),
+            AstNode::MultiFunction { func, args } => {
+                let args: Vec<String> = args.iter().map(|arg| arg.to_latex()).collect();
+                match (func.name(), args.as_slice()) {
+                    ("ncr", [n, r]) => format!("\\binom{{{}}}{{{}}}", n, r),
+                    (name, args) => function_latex(name, &args.join(", ")),
+                }
+            }
+            AstNode::PercentChange { left, op, percent } => match op {
+                Token::Plus => format!("{} + {}", left.to_latex(), percent.to_latex()),
+                Token::Minus => format!("{} - {}", left.to_latex(), percent.to_latex()),
+                _ => panic!("Unexpected operator in percentage change: {:?}", op),
+            },
+        }
+    }
+
+    /// How tightly the node binds, used to decide on parentheses.
+    fn precedence(&self) -> u8 {
+        match self {
+            AstNode::BinaryOp { op: Token::Plus | Token::Minus, .. } => 0,
+            AstNode::PercentChange { .. } => 0,
+            AstNode::BinaryOp { op: Token::Exponent, .. } => 2,
+            AstNode::BinaryOp { .. } => 1,
+            AstNode::UnaryOp { op: Token::Minus, .. } => 1,
+            AstNode::Number(value) if *value < 0.0 => 1,
+            _ => 3,
+        }
+    }
+
+    /// The LaTeX for an operand, in parentheses if it binds less tightly than `precedence`.
+    fn latex_operand(&self, precedence: u8) -> String {
+        if self.precedence() < precedence {
+            format!("\\left({}\\right)", self.to_latex())
+        } else {
+            self.to_latex()
+        }
+    }
+}
+
+fn function_latex(name: &str, argument: &str) -> String {
+    match name {
+        "sqrt" => format!("\\sqrt{{{}}}", argument),
+        "abs" => format!("\\left|{}\\right|", argument),
+        "factorial" => format!("\\left({}\\right)!", argument),
+        "ceil" => format!("\\left\\lceil {}\\right\\rceil", argument),
+        "floor" => format!("\\left\\lfloor {}\\right\\rfloor", argument),
+        "cosec" => format!("\\csc\\left({}\\right)", argument),
+        "gamma" => format!("\\Gamma\\left({}\\right)", argument),
+        "lgamma" => format!("\\ln\\left|\\Gamma\\left({}\\right)\\right|", argument),
+        "sin" | "cos" | "tan" | "sec" | "cot" | "sinh" | "cosh" | "tanh" | "ln" | "log" | "exp" => {
+            format!("\\{}\\left({}\\right)", name, argument)
+        }
+        "asin" | "acos" | "atan" => format!("\\arc{}\\left({}\\right)", &name[1..], argument),
+        _ => format!("\\operatorname{{{}}}\\left({}\\right)", name, argument),
+    }
+}
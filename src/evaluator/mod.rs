@@ -26,16 +26,23 @@ use std::time::Instant;
 
 use log::info;
 
+use crate::conversions::{find_unit_by_symbol, get_all_units, Unit};
 use crate::evaluator::constants::Constant;
-use crate::evaluator::functions::Function;
+use crate::evaluator::functions::{Function, MultiFunction};
 use crate::evaluator::parser::Parser;
-use crate::evaluator::tokeniser::tokenize;
+use crate::evaluator::quantity::Quantity;
+use crate::evaluator::tokeniser::{paren_depth, tokenize};
 use crate::history;
 
 pub(crate) mod functions;
 pub(crate) mod parser;
 pub(crate) mod tokeniser;
 pub(crate) mod constants;
+pub(crate) mod decimal;
+pub(crate) mod latex;
+pub(crate) mod quantity;
+pub(crate) mod user_constants;
+pub(crate) mod script;
 
 #[derive(Clone, Debug)]
 pub(crate) enum Token {
@@ -44,28 +51,37 @@ pub(crate) enum Token {
     Minus,
     Multiply,
     Divide,
+    FloorDivide,
     Exponent,
+    Percent,
     OpenParen,
     CloseParen,
+    Comma,
     UnaryFunction(Rc<Function>),
+    /// The `√` prefix, which applies sqrt to what follows, e.g. `√16`
+    SquareRoot(Rc<Function>),
+    MultiFunction(Rc<MultiFunction>),
+    Unit(&'static Unit),
 }
 
 impl Token {
-    pub(crate) fn perform_binary(&self, left: f64, right: f64, _mode: &AngleMode) -> f64 {
+    pub(crate) fn perform_binary(&self, left: Quantity, right: Quantity, _mode: &AngleMode) -> Result<Quantity, String> {
         match self {
-            Token::Plus => left + right,
-            Token::Minus => left - right,
-            Token::Multiply => left * right,
-            Token::Divide => left / right,
-            Token::Exponent => left.powf(right),
+            Token::Plus => left.add(right),
+            Token::Minus => left.subtract(right),
+            Token::Multiply => left.multiply(right),
+            Token::Divide => left.divide(right),
+            Token::FloorDivide => Ok(left.divide(right)?.map(f64::floor)),
+            Token::Exponent => Ok(Quantity::number(left.plain()?.powf(right.plain()?))),
             _ => panic!("Unexpected operator in binary operation: {:?}", self),
         }
     }
 
-    pub(crate) fn perform_unary(&self, val: f64, mode: &AngleMode) -> f64 {
+    pub(crate) fn perform_unary(&self, val: Quantity, mode: &AngleMode) -> Result<Quantity, String> {
         match self {
-            Token::Minus => -val,
-            Token::UnaryFunction(f) => f.evaluate(val, mode),
+            Token::Minus => Ok(val.map(|v| -v)),
+            Token::Percent => Ok(val.map(|v| v / 100.0)),
+            Token::UnaryFunction(f) => f.evaluate(val.plain()?, mode).map(Quantity::number),
             _ => panic!("Unexpected operator in unary operation: {:?}", self),
         }
     }
@@ -74,6 +90,7 @@ impl Token {
 #[derive(Clone, Debug)]
 pub(crate) enum AstNode {
     Number(f64),
+    Quantity(f64, &'static Unit),
     UnaryOp {
         op: Token,
         expr: Box<AstNode>,
@@ -87,26 +104,89 @@ pub(crate) enum AstNode {
         func: Rc<Function>,
         expr: Box<AstNode>,
     },
+    MultiFunction {
+        func: Rc<MultiFunction>,
+        args: Vec<AstNode>,
+    },
+    /// Adding or subtracting a percentage is a percentage of the left hand side, like a typical
+    /// calculator, so `50 + 10%` is `50 + 50 * 10%` = 55. It is kept as it was written and only
+    /// worked out when evaluated.
+    PercentChange {
+        left: Box<AstNode>,
+        op: Token,
+        percent: Box<AstNode>,
+    },
 }
 
 impl AstNode {
-    pub(crate) fn evaluate(&self, mode: &AngleMode) -> f64 {
+    pub(crate) fn evaluate(&self, mode: &AngleMode) -> Result<Quantity, String> {
+        self.evaluate_with(mode, false)
+    }
+
+    /// Evaluate the tree. When `integers` is set the result of each arithmetic operation is
+    /// truncated to a whole number, so that division is integer division, e.g. `7 / 2 = 3`.
+    /// Percentages are left alone until they are applied, so `50 + 10%` is still 55.
+    pub(crate) fn evaluate_with(&self, mode: &AngleMode, integers: bool) -> Result<Quantity, String> {
         match self {
-            AstNode::Number(value) => *value,
+            AstNode::Number(value) => Ok(Quantity::number(*value)),
+            AstNode::Quantity(value, unit) => Ok(Quantity::with_unit(*value, unit)),
             AstNode::UnaryOp { op, expr } => {
-                let value = expr.evaluate(mode);
+                let value = expr.evaluate_with(mode, integers)?;
                 op.perform_unary(value, mode)
             }
             AstNode::BinaryOp { left, op, right } => {
-                let left_val = left.evaluate(mode);
-                let right_val = right.evaluate(mode);
-                op.perform_binary(left_val, right_val, mode)
+                let left_val = left.evaluate_with(mode, integers)?;
+                let right_val = right.evaluate_with(mode, integers)?;
+                op.perform_binary(left_val, right_val, mode).map(|q| truncate(q, integers))
+            }
+            AstNode::Function { func, expr } => {
+                let value = expr.evaluate_with(mode, integers)?.plain()?;
+                func.evaluate(value, mode).map(Quantity::number)
+            }
+            AstNode::MultiFunction { func, args } => {
+                let values = args.iter()
+                    .map(|arg| arg.evaluate_with(mode, integers)?.plain())
+                    .collect::<Result<Vec<f64>, String>>()?;
+                func.evaluate(&values, mode).map(|value| match func.unit() {
+                    Some(unit) => Quantity::with_unit(value, unit),
+                    None => Quantity::number(value),
+                })
+            }
+            AstNode::PercentChange { left, op, percent } => {
+                let left_val = left.evaluate_with(mode, integers)?;
+                let percent_val = percent.evaluate_with(mode, integers)?;
+                let change = Token::Multiply.perform_binary(left_val.clone(), percent_val, mode)?;
+                op.perform_binary(left_val, change, mode).map(|q| truncate(q, integers))
             }
-            AstNode::Function { func, expr } => func.evaluate(expr.evaluate(mode), mode),
         }
     }
 }
 
+/// Truncate to a whole number in integer mode.
+fn truncate(quantity: Quantity, integers: bool) -> Quantity {
+    if integers {
+        quantity.map(f64::trunc)
+    } else {
+        quantity
+    }
+}
+
+/// Parse the units of a conversion, e.g. `ft`, or compound units like `ft+in` or `ft in`.
+fn parse_target(target: &str) -> Result<Vec<&'static Unit>, String> {
+    let target = target.trim();
+    if let Some(unit) = find_unit_by_symbol(target) {
+        return Ok(vec![unit]);
+    }
+    let parts: Vec<&str> = if target.contains('+') {
+        target.split('+').collect()
+    } else {
+        target.split_whitespace().collect()
+    };
+    parts.iter()
+        .map(|part| find_unit_by_symbol(part.trim()).ok_or_else(|| format!("Unknown unit '{}'", part.trim())))
+        .collect()
+}
+
 #[derive(Debug)]
 pub(crate) enum AngleMode {
     Degrees,
@@ -145,7 +225,15 @@ impl AngleMode {
 pub(crate) struct Evaluator<'a> {
     angle_mode: &'a AngleMode,
     function_register: Vec<Function>,
-    constant_register: Vec<&'static Constant>,
+    multi_function_register: Vec<MultiFunction>,
+    constant_register: Vec<Constant>,
+    unit_register: Vec<&'static Unit>,
+    decimals: bool,
+    durations: bool,
+    decimal_comma: bool,
+    integers: bool,
+    /// Named values, such as those set in a worksheet
+    variables: Vec<(String, f64)>,
 }
 
 impl<'a> Evaluator<'a> {
@@ -153,7 +241,14 @@ impl<'a> Evaluator<'a> {
         Self {
             angle_mode,
             function_register: functions::get_all(),
+            multi_function_register: functions::get_all_multi(),
             constant_register: constants::get_all(),
+            unit_register: vec![],
+            decimals: false,
+            durations: false,
+            decimal_comma: false,
+            integers: false,
+            variables: vec![],
         }
     }
 
@@ -161,22 +256,55 @@ impl<'a> Evaluator<'a> {
         Self::create(mode)
     }
 
+    /// Allow numbers in expressions to be followed by a unit symbol, e.g. `5 km + 500 m`.
+    pub(crate) fn with_quantities(mut self, quantities: bool) -> Self {
+        self.unit_register = if quantities { get_all_units() } else { vec![] };
+        self
+    }
+
+    /// Use exact decimal arithmetic rather than floating point.
+    pub(crate) fn with_decimals(mut self, decimals: bool) -> Self {
+        self.decimals = decimals;
+        self
+    }
+
+    /// Allow durations in hours, minutes and seconds, e.g. `1:30:00 + 0:45`. They are
+    /// evaluated in seconds.
+    pub(crate) fn with_durations(mut self, durations: bool) -> Self {
+        self.durations = durations;
+        self
+    }
+
+    /// Read a comma in a number as the decimal point, e.g. `3,14`. Function arguments can
+    /// then be separated with `;`, e.g. `max(1,5; 2)`.
+    pub(crate) fn with_decimal_comma(mut self, decimal_comma: bool) -> Self {
+        self.decimal_comma = decimal_comma;
+        self
+    }
+
+    /// Work only in whole numbers, truncating every intermediate result like a basic
+    /// integer calculator, e.g. `7 / 2 = 3`. This takes precedence over decimal arithmetic.
+    pub(crate) fn with_integers(mut self, integers: bool) -> Self {
+        self.integers = integers;
+        self
+    }
+
     pub(crate) fn evaluate(&self, expression: &str) -> Result<f64, String> {
+        self.evaluate_quantity(expression).map(|q| q.value)
+    }
+
+    /// Evaluate an expression, keeping the unit of the result if it has one.
+    pub(crate) fn evaluate_quantity(&self, expression: &str) -> Result<Quantity, String> {
 
         let t_start = Instant::now();
 
-        if expression.is_empty() {
-            return Err("Please supply an expression to evaluate".to_string());
-        }
-        tokenize(expression, &self)
-            .and_then(|tokens| {
-                let mut parser = Parser::new(tokens);
-                let ast = parser.parse()?;
-                Ok(ast.evaluate(&self.angle_mode))
-            })
+        self.calculate(expression)
             .inspect(|result| {
-                history::manager().add((&expression, &result));
-                history::manager().save();
+                // Results that aren't numbers, like inf - inf, aren't worth recalling
+                if result.value.is_finite() {
+                    history::manager().add((&expression, &result.value), &self.angle_mode.to_string());
+                    history::manager().save();
+                }
                 let duration = Instant::now().duration_since(t_start);
                 info!("Evaluated \"{}\" in {} micro seconds", expression.trim(), duration.as_micros());
             })
@@ -186,10 +314,129 @@ impl<'a> Evaluator<'a> {
             })
 
     }
+
+    /// Evaluate an expression to preview the result as it is typed. Unlike `evaluate`,
+    /// nothing is recorded in history or logged.
+    pub(crate) fn evaluate_preview(&self, expression: &str) -> Result<Quantity, String> {
+        self.calculate(expression)
+    }
+
+    fn calculate(&self, expression: &str) -> Result<Quantity, String> {
+        if expression.is_empty() {
+            return Err("Please supply an expression to evaluate".to_string());
+        }
+        let (expression_part, target) = self.split_conversion(expression);
+        tokenize(expression_part, &self)
+            .and_then(|tokens| {
+                let mut parser = Parser::new(tokens);
+                let ast = parser.parse()?;
+                if self.integers {
+                    ast.evaluate_with(&self.angle_mode, true)
+                } else if self.decimals {
+                    ast.evaluate_decimal(&self.angle_mode)
+                        .map(|(value, approximate)| Quantity::exact(value, approximate))
+                } else {
+                    ast.evaluate(&self.angle_mode)
+                }
+            })
+            .and_then(|result| match target {
+                Some(target) => result.convert_to(parse_target(target)?),
+                None => Ok(result),
+            })
+    }
+
+    /// Check whether an expression is complete, that is its parentheses are balanced and
+    /// it parses successfully. The expression is not evaluated and nothing is recorded in history.
+    pub(crate) fn is_complete(&self, expression: &str) -> bool {
+        if expression.trim().is_empty() || paren_depth(expression) != 0 {
+            return false;
+        }
+        let (expression, _) = self.split_conversion(expression);
+        tokenize(expression, &self)
+            .and_then(|tokens| Parser::new(tokens).parse())
+            .is_ok()
+    }
+
+    /// Check whether the text could be the start of a valid expression, so obviously wrong input
+    /// can be flagged while it is typed. Every word must be, or begin, the name of a function,
+    /// constant or unit. Nothing else is checked.
+    pub(crate) fn is_plausible_prefix(&self, text: &str) -> bool {
+        let mut names: Vec<&str> = self.function_register.iter().map(|f| f.name())
+            .chain(self.multi_function_register.iter().map(|f| f.name()))
+            .chain(self.constant_register.iter().map(|c| c.name()))
+            .chain(self.unit_register.iter().map(|u| u.symbol))
+            .collect();
+        if !self.unit_register.is_empty() {
+            names.extend(["in", "to"]);
+        }
+        let chars: Vec<char> = text.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            if !chars[i].is_alphabetic() {
+                i += 1;
+                continue;
+            }
+            // Names like total_with_tip are one word
+            let start = i;
+            while i < chars.len() && (chars[i].is_alphabetic() || chars[i] == '_') {
+                i += 1;
+            }
+            let word: String = chars[start..i].iter().collect();
+            // The exponent of a number in scientific notation, e.g. 1e5
+            let exponent = (word == "e" || word == "E") && start > 0 && chars[start - 1].is_ascii_digit();
+            if !exponent && !names.iter().any(|name| name.starts_with(&word)) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Write an expression as LaTeX. The expression is not evaluated.
+    pub(crate) fn to_latex(&self, expression: &str) -> Result<String, String> {
+        let (expression, _) = self.split_conversion(expression);
+        tokenize(expression, &self)
+            .and_then(|tokens| Parser::new(tokens).parse())
+            .map(|ast| ast.to_latex())
+    }
+
+    /// With quantities, an expression can end with a conversion, e.g. `1.83 m in ft+in`.
+    /// Split off the units to convert to.
+    fn split_conversion<'e>(&self, expression: &'e str) -> (&'e str, Option<&'e str>) {
+        if self.unit_register.is_empty() {
+            return (expression, None);
+        }
+        [" in ", " to "].iter()
+            .filter_map(|keyword| expression.rfind(keyword).map(|i| (i, keyword.len())))
+            .max()
+            .map_or((expression, None), |(i, len)| (&expression[..i], Some(&expression[i + len..])))
+    }
+
     pub fn function_register(&self) -> &Vec<Function> {
         &self.function_register
     }
-    pub fn constant_register(&self) -> &Vec<&'static Constant> {
+    pub fn multi_function_register(&self) -> &Vec<MultiFunction> {
+        &self.multi_function_register
+    }
+    pub fn constant_register(&self) -> &Vec<Constant> {
         &self.constant_register
     }
+    pub fn unit_register(&self) -> &Vec<&'static Unit> {
+        &self.unit_register
+    }
+
+    pub fn durations(&self) -> bool {
+        self.durations
+    }
+
+    pub fn decimal_comma(&self) -> bool {
+        self.decimal_comma
+    }
+
+    pub fn integers(&self) -> bool {
+        self.integers
+    }
+
+    pub fn variables(&self) -> &Vec<(String, f64)> {
+        &self.variables
+    }
 }
@@ -33,8 +33,15 @@ impl Parser {
         Parser { tokens, current: 0 }
     }
 
+    /// Parse the whole expression. Anything left over, like the `5` of `sin(30) 5`, is an error
+    /// rather than being quietly ignored.
     pub(crate) fn parse(&mut self) -> Result<AstNode, String> {
-        self.parse_expression()
+        let node = self.parse_expression()?;
+        match self.peek_token() {
+            None => Ok(node),
+            Some(Token::CloseParen) => Err(String::from("Unmatched closing parenthesis")),
+            Some(token) => Err(format!("Unexpected token: {:?}", token)),
+        }
     }
 
     fn parse_expression(&mut self) -> Result<AstNode, String> {
@@ -50,10 +57,18 @@ impl Parser {
                     self.consume_token();
                     let op = token.clone();
                     let right = self.parse_mul_div()?;
-                    node = AstNode::BinaryOp {
-                        left: Box::new(node),
-                        op,
-                        right: Box::new(right),
+                    node = if matches!(right, AstNode::UnaryOp { op: Token::Percent, .. }) {
+                        AstNode::PercentChange {
+                            left: Box::new(node),
+                            op,
+                            percent: Box::new(right),
+                        }
+                    } else {
+                        AstNode::BinaryOp {
+                            left: Box::new(node),
+                            op,
+                            right: Box::new(right),
+                        }
                     };
                 }
                 _ => break,
@@ -68,7 +83,7 @@ impl Parser {
 
         while let Some(token) = self.peek_token() {
             match token {
-                Token::Multiply | Token::Divide => {
+                Token::Multiply | Token::Divide | Token::FloorDivide => {
                     self.consume_token();
                     let op = token.clone();
                     let right = self.parse_exponent()?;
@@ -85,14 +100,14 @@ impl Parser {
         Ok(node)
     }
     fn parse_exponent(&mut self) -> Result<AstNode, String> {
-        let mut node = self.parse_primary()?;
+        let mut node = self.parse_percent()?;
 
         while let Some(token) = self.peek_token() {
             match token {
                 Token::Exponent => {
                     self.consume_token();
                     let op = token.clone();
-                    let right = self.parse_primary()?;
+                    let right = self.parse_percent()?;
                     node = AstNode::BinaryOp {
                         left: Box::new(node),
                         op,
@@ -106,10 +121,31 @@ impl Parser {
         Ok(node)
     }
 
+    /// A postfix percent divides by 100, so 200 * 15% is 30 and 25% alone is 0.25.
+    fn parse_percent(&mut self) -> Result<AstNode, String> {
+        let mut node = self.parse_primary()?;
+
+        while let Some(Token::Percent) = self.peek_token() {
+            self.consume_token();
+            node = AstNode::UnaryOp {
+                op: Token::Percent,
+                expr: Box::new(node),
+            };
+        }
+
+        Ok(node)
+    }
+
     fn parse_primary(&mut self) -> Result<AstNode, String> {
         if let Some(token) = self.next_token() {
             match token {
-                Token::Number(value) => Ok(AstNode::Number(value)),
+                Token::Number(value) => match self.peek_token() {
+                    Some(Token::Unit(unit)) => {
+                        self.consume_token();
+                        Ok(AstNode::Quantity(value, unit))
+                    }
+                    _ => Ok(AstNode::Number(value)),
+                },
                 Token::OpenParen => {
                     let node = self.parse_expression();
                     match self.next_token() {
@@ -125,6 +161,15 @@ impl Parser {
                         expr: Box::new(expr),
                     })
                 }
+                // A unary plus changes nothing, but is accepted as other tools write it
+                Token::Plus => self.parse_primary(),
+                Token::SquareRoot(func) => {
+                    let expr = self.parse_primary()?;
+                    Ok(AstNode::Function {
+                        func,
+                        expr: Box::new(expr),
+                    })
+                }
                 Token::UnaryFunction(func) => {
                     let option = self.next_token();
                     match option {
@@ -145,6 +190,30 @@ impl Parser {
                         expr: Box::new(expr),
                     })
                 }
+                Token::MultiFunction(func) => {
+                    match self.next_token() {
+                        Some(Token::OpenParen) => (),
+                        _ => {
+                            return Err(String::from(
+                                "Function must be followed by opening parenthesis",
+                            ))
+                        }
+                    }
+                    let mut args = Vec::new();
+                    if let Some(Token::CloseParen) = self.peek_token() {
+                        self.consume_token();
+                    } else {
+                        loop {
+                            args.push(self.parse_expression()?);
+                            match self.next_token() {
+                                Some(Token::Comma) => (),
+                                Some(Token::CloseParen) => break,
+                                _ => return Err(String::from("Unmatched opening parenthesis")),
+                            }
+                        }
+                    }
+                    Ok(AstNode::MultiFunction { func, args })
+                }
                 _ => Err(format!("Unexpected token: {:?}", token)),
             }
         } else {
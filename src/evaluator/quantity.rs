@@ -0,0 +1,145 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use rust_decimal::Decimal;
+use rust_decimal::prelude::ToPrimitive;
+
+use crate::conversions::{convert, format_compound, Dimension, Unit};
+use crate::conversions::data::BYTE;
+use crate::conversions::data_rate::{download_time, BIT_PER_SECOND};
+use crate::conversions::time::SECOND;
+
+/// A value that may carry a unit. Plain numbers have no unit.
+/// Quantities can be added and subtracted if they have the same dimension, the result taking the
+/// unit of the left hand side. They can be multiplied or divided by plain numbers, and dividing
+/// two quantities of the same dimension gives a plain ratio. Dividing data by a data rate gives the
+/// transfer time.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Quantity {
+    pub(crate) value: f64,
+    pub(crate) unit: Option<&'static Unit>,
+    /// The exact value, when evaluated with decimal arithmetic
+    pub(crate) exact: Option<Decimal>,
+    /// Whether the exact value fell back to floating point, e.g. for a function
+    pub(crate) approximate: bool,
+    /// Units to display the value in, largest first, e.g. feet and inches
+    pub(crate) compound: Vec<&'static Unit>,
+}
+
+impl Quantity {
+    fn new(value: f64, unit: Option<&'static Unit>) -> Self {
+        Self { value, unit, exact: None, approximate: false, compound: vec![] }
+    }
+
+    pub(crate) fn number(value: f64) -> Self {
+        Self::new(value, None)
+    }
+
+    pub(crate) fn with_unit(value: f64, unit: &'static Unit) -> Self {
+        Self::new(value, Some(unit))
+    }
+
+    pub(crate) fn exact(value: Decimal, approximate: bool) -> Self {
+        Self { exact: Some(value), approximate, ..Self::number(value.to_f64().unwrap_or(f64::NAN)) }
+    }
+
+    /// Convert to the given units. A single unit gives a plain conversion, several units, largest
+    /// first, split the value between them for display, e.g. `6 ft 0 in`.
+    pub(crate) fn convert_to(self, units: Vec<&'static Unit>) -> Result<Quantity, String> {
+        let from = self.unit.ok_or_else(|| String::from("Only quantities with units can be converted"))?;
+        let to = *units.first().ok_or_else(|| String::from("No unit to convert to"))?;
+        if let Some(unit) = units.iter().find(|u| u.dimension() != from.dimension()) {
+            return Err(format!("Cannot convert {} to {}", from.dimension(), unit.dimension()));
+        }
+        if units.windows(2).any(|pair| convert(&1.0, pair[0], pair[1]) <= 1.0) {
+            return Err(String::from("Compound units must be given largest first"));
+        }
+        let value = convert(&self.value, from, to);
+        Ok(Quantity { compound: if units.len() > 1 { units } else { vec![] }, ..Quantity::with_unit(value, to) })
+    }
+
+    /// Display the value split into its compound units, if it has them.
+    pub(crate) fn compound_display(&self) -> Option<String> {
+        match (self.unit, self.compound.is_empty()) {
+            (Some(unit), false) => Some(format_compound(self.value, unit, &self.compound)),
+            _ => None,
+        }
+    }
+
+    /// Get the value of a plain number, for operations that make no sense for quantities.
+    pub(crate) fn plain(&self) -> Result<f64, String> {
+        match self.unit {
+            None => Ok(self.value),
+            Some(unit) => Err(format!("Expected a plain number, but found a quantity in {}", unit.name)),
+        }
+    }
+
+    /// Apply a function to the value, keeping the unit.
+    pub(crate) fn map(self, f: impl Fn(f64) -> f64) -> Self {
+        Self::new(f(self.value), self.unit)
+    }
+
+    pub(crate) fn add(self, other: Quantity) -> Result<Quantity, String> {
+        self.add_sub(other, |a, b| a + b)
+    }
+
+    pub(crate) fn subtract(self, other: Quantity) -> Result<Quantity, String> {
+        self.add_sub(other, |a, b| a - b)
+    }
+
+    fn add_sub(self, other: Quantity, op: fn(f64, f64) -> f64) -> Result<Quantity, String> {
+        match (self.unit, other.unit) {
+            (None, None) => Ok(Quantity::number(op(self.value, other.value))),
+            (Some(u1), Some(u2)) if u1.dimension() == u2.dimension() => {
+                Ok(Quantity::with_unit(op(self.value, convert(&other.value, u2, u1)), u1))
+            }
+            (Some(u1), Some(u2)) => Err(format!("Cannot add or subtract {} and {}", u1.dimension(), u2.dimension())),
+            _ => Err(String::from("Cannot add or subtract a plain number and a quantity")),
+        }
+    }
+
+    pub(crate) fn multiply(self, other: Quantity) -> Result<Quantity, String> {
+        match (self.unit, other.unit) {
+            (Some(_), Some(_)) => Err(String::from("Quantities can only be multiplied by a plain number")),
+            (unit, None) | (None, unit) => Ok(Quantity::new(self.value * other.value, unit)),
+        }
+    }
+
+    pub(crate) fn divide(self, other: Quantity) -> Result<Quantity, String> {
+        if other.value == 0.0 {
+            return Err(String::from("Division by zero"));
+        }
+        match (self.unit, other.unit) {
+            (unit, None) => Ok(Quantity::new(self.value / other.value, unit)),
+            (Some(u1), Some(u2)) if u1.dimension() == u2.dimension() => {
+                Ok(Quantity::number(self.value / convert(&other.value, u2, u1)))
+            }
+            (Some(u1), Some(u2)) if *u1.dimension() == Dimension::Data && *u2.dimension() == Dimension::DataRate => {
+                let bytes = convert(&self.value, u1, &BYTE);
+                let rate = convert(&other.value, u2, &BIT_PER_SECOND);
+                Ok(Quantity::with_unit(download_time(bytes, rate), &SECOND))
+            }
+            (Some(u1), Some(u2)) => Err(format!("Cannot divide {} by {}", u1.dimension(), u2.dimension())),
+            (None, Some(_)) => Err(String::from("Cannot divide a plain number by a quantity")),
+        }
+    }
+}
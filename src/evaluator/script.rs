@@ -0,0 +1,97 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use std::fs;
+use std::path::Path;
+
+use crate::evaluator::{constants, functions, Evaluator};
+
+/// The name of the variable holding the result of the previous line
+static ANSWER: &str = "ans";
+
+/// One line of a worksheet and its result. Assignments give the value assigned.
+#[derive(Debug, PartialEq)]
+pub(crate) struct ScriptLine {
+    pub(crate) line: String,
+    pub(crate) result: Result<f64, String>,
+}
+
+impl<'a> Evaluator<'a> {
+    /// Evaluate a worksheet of one expression per line. A line can set a variable to use in
+    /// the lines that follow, e.g. `rate = 0.07`, and `ans` is the result of the line before.
+    /// Blank lines and lines starting with `#` are skipped. Nothing is recorded in history.
+    pub(crate) fn run_script(mut self, script: &str) -> Vec<ScriptLine> {
+        let mut lines = Vec::new();
+        for line in script.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let result = match split_assignment(line) {
+                Some((name, expression)) => check_variable_name(name)
+                    .and_then(|_| self.calculate(expression).map(|q| q.value))
+                    .inspect(|value| self.set_variable(name, *value)),
+                None => self.calculate(line).map(|q| q.value),
+            };
+            if let Ok(value) = result {
+                self.set_variable(ANSWER, value);
+            }
+            lines.push(ScriptLine { line: line.to_string(), result });
+        }
+        lines
+    }
+
+    /// Read a worksheet from a file and evaluate it.
+    pub(crate) fn run_file(self, path: &Path) -> Result<Vec<ScriptLine>, String> {
+        let script = fs::read_to_string(path)
+            .map_err(|e| format!("Unable to read {}: {}", path.display(), e))?;
+        Ok(self.run_script(&script))
+    }
+
+    fn set_variable(&mut self, name: &str, value: f64) {
+        match self.variables.iter_mut().find(|(n, _)| n == name) {
+            Some(variable) => variable.1 = value,
+            None => self.variables.push((name.to_string(), value)),
+        }
+    }
+}
+
+/// Split an assignment such as `rate = 0.07` into the variable name and its expression.
+fn split_assignment(line: &str) -> Option<(&str, &str)> {
+    let (name, expression) = line.split_once('=')?;
+    Some((name.trim(), expression.trim()))
+}
+
+/// Variable names must start with a letter, and can't hide a function or built in constant.
+fn check_variable_name(name: &str) -> Result<(), String> {
+    let mut chars = name.chars();
+    if !chars.next().is_some_and(char::is_alphabetic) || !chars.all(char::is_alphanumeric) {
+        return Err(format!("'{}' is not a valid variable name", name));
+    }
+    if constants::get_builtin().iter().any(|c| c.name() == name) {
+        return Err(format!("'{}' is a built in constant", name));
+    }
+    if functions::get_all().iter().any(|f| f.name() == name)
+        || functions::get_all_multi().iter().any(|f| f.name() == name) {
+        return Err(format!("'{}' is a function", name));
+    }
+    Ok(())
+}
@@ -20,6 +20,8 @@
  *
  */
 
+use std::rc::Rc;
+
 use log::warn;
 use crate::evaluator::{Evaluator, Token};
 
@@ -32,24 +34,59 @@ pub(crate) fn tokenize(expression: &str, evaluator: &Evaluator) -> Result<Vec<To
         match chars[i] {
             '0'..='9' | '.' => {
                 let mut num_str = String::new();
-                while i < chars.len() && (chars[i].is_digit(10) || chars[i] == '.') {
-                    num_str.push(chars[i]);
+                while i < chars.len() && (chars[i].is_digit(10) || chars[i] == '.' || is_decimal_comma(&chars, i, evaluator)) {
+                    num_str.push(if chars[i] == ',' { '.' } else { chars[i] });
                     i += 1;
                 }
+                // An exponent, e.g. 1.5e3 or 2E-4, must have digits, otherwise the e is not part of the number
+                if let Some(consumed) = exponent_length(&chars, i) {
+                    num_str.extend(&chars[i..i + consumed]);
+                    i += consumed;
+                }
                 let number = num_str.parse::<f64>().unwrap_or_else(|e| {
                     warn!("{}", e);
                     0.0
                 });
+                if let Some((degrees, consumed)) = parse_dms(&chars, i, number) {
+                    tokens.push(Token::Number(degrees));
+                    i += consumed;
+                    continue;
+                }
+                if let Some((seconds, consumed)) = parse_duration(&chars, i, number).filter(|_| evaluator.durations()) {
+                    tokens.push(Token::Number(seconds));
+                    i += consumed;
+                    continue;
+                }
                 tokens.push(Token::Number(number));
+                if let Some((token, consumed)) = parse_units(&chars, chars.len(), i, &evaluator) {
+                    tokens.push(token);
+                    i += consumed;
+                }
                 continue; // Skip the increment below because it's already done
             }
             '+' => tokens.push(Token::Plus),
             '-' => tokens.push(Token::Minus),
             '*' => tokens.push(Token::Multiply),
-            '/' => tokens.push(Token::Divide),
+            '/' => {
+                // `//` is floor division, not two divides
+                if chars.get(i + 1) == Some(&'/') {
+                    tokens.push(Token::FloorDivide);
+                    i += 1;
+                } else {
+                    tokens.push(Token::Divide);
+                }
+            }
             '^' => tokens.push(Token::Exponent),
+            '%' => tokens.push(Token::Percent),
             '(' => tokens.push(Token::OpenParen),
             ')' => tokens.push(Token::CloseParen),
+            ',' | ';' => tokens.push(Token::Comma),
+            '√' => {
+                let sqrt = evaluator.function_register().iter()
+                    .find(|f| f.name() == "sqrt")
+                    .ok_or_else(|| String::from("sqrt is not available"))?;
+                tokens.push(Token::SquareRoot(Rc::new(sqrt.clone())));
+            }
             ' ' | '\n' => {} // Ignore spaces
             _ => {
                 // We now look for a function.
@@ -60,7 +97,8 @@ pub(crate) fn tokenize(expression: &str, evaluator: &Evaluator) -> Result<Vec<To
                         continue;
                     }
                     None => {
-                        match parse_constants(&chars, chars.len(), i, &evaluator) {
+                        match parse_variables(&chars, chars.len(), i, &evaluator)
+                            .or_else(|| parse_constants(&chars, chars.len(), i, &evaluator)) {
                             Some((token, consumed)) => {
                                 tokens.push(token);
                                 i += consumed;
@@ -80,6 +118,36 @@ pub(crate) fn tokenize(expression: &str, evaluator: &Evaluator) -> Result<Vec<To
     Ok(tokens)
 }
 
+/// Whether the character at `i` is a comma used as the decimal point. It must be followed by a
+/// digit, so `max(1, 2)` still has two arguments.
+fn is_decimal_comma(chars: &Vec<char>, i: usize, evaluator: &Evaluator) -> bool {
+    evaluator.decimal_comma() && chars[i] == ','
+        && chars.get(i + 1).is_some_and(|c| c.is_ascii_digit())
+}
+
+/// The number of characters in an exponent starting at `i`, like `e3` or `E-4`, if there is one.
+fn exponent_length(chars: &Vec<char>, i: usize) -> Option<usize> {
+    if !matches!(chars.get(i), Some('e') | Some('E')) {
+        return None;
+    }
+    let sign = if matches!(chars.get(i + 1), Some('+') | Some('-')) { 1 } else { 0 };
+    let digits = chars[(i + 1 + sign).min(chars.len())..].iter()
+        .take_while(|c| c.is_digit(10))
+        .count();
+    if digits > 0 { Some(1 + sign + digits) } else { None }
+}
+
+/// Count the parentheses in an expression, returning the number of opening parentheses
+/// that have not yet been closed. A negative value means there are more closing than opening
+/// parentheses.
+pub(crate) fn paren_depth(expression: &str) -> i32 {
+    expression.chars().fold(0, |depth, c| match c {
+        '(' => depth + 1,
+        ')' => depth - 1,
+        _ => depth,
+    })
+}
+
 fn parse_functions(
     chars: &Vec<char>,
     exp_len: usize,
@@ -91,8 +159,114 @@ fn parse_functions(
             return Some((token, consumed));
         }
     }
+    for function in evaluator.multi_function_register() {
+        if let Some((token, consumed)) = function.is_token(chars, &exp_len, &i) {
+            return Some((token, consumed));
+        }
+    }
     None
 }
+/// Angles can be entered in degrees, minutes and seconds, e.g. `12°30'15"`. Given the degrees
+/// and the position following them, combine any minutes and seconds into decimal degrees.
+/// A `°` followed by a letter is a unit such as `°C`, not an angle.
+fn parse_dms(chars: &Vec<char>, i: usize, degrees: f64) -> Option<(f64, usize)> {
+    if chars.get(i) != Some(&'°') || chars.get(i + 1).is_some_and(|c| c.is_alphabetic()) {
+        return None;
+    }
+    let mut value = degrees;
+    let mut j = i + 1;
+    for (marker, divisor) in [('\'', 60.0), ('"', 3600.0)] {
+        let start = j;
+        while j < chars.len() && (chars[j].is_digit(10) || chars[j] == '.') {
+            j += 1;
+        }
+        match chars[start..j].iter().collect::<String>().parse::<f64>() {
+            Ok(part) if chars.get(j) == Some(&marker) => {
+                value += part / divisor;
+                j += 1;
+            }
+            _ => {
+                j = start;
+                break;
+            }
+        }
+    }
+    Some((value, j - i))
+}
+
+/// Durations can be entered as hours and minutes, or hours, minutes and seconds, e.g. `1:30`
+/// or `1:30:15`. Given the hours and the position following them, give the duration in seconds.
+fn parse_duration(chars: &Vec<char>, i: usize, hours: f64) -> Option<(f64, usize)> {
+    let mut seconds = hours * 3600.0;
+    let mut j = i;
+    for multiplier in [60.0, 1.0] {
+        if chars.get(j) != Some(&':') {
+            break;
+        }
+        let start = j + 1;
+        let mut end = start;
+        while end < chars.len() && (chars[end].is_digit(10) || chars[end] == '.') {
+            end += 1;
+        }
+        let part = chars[start..end].iter().collect::<String>().parse::<f64>().ok()?;
+        seconds += part * multiplier;
+        j = end;
+    }
+    if j == i {
+        None
+    } else {
+        Some((seconds, j - i))
+    }
+}
+
+/// Look for a unit symbol following a number, allowing for spaces in between. The longest
+/// matching symbol wins, so `m²` is not read as `m`.
+fn parse_units(
+    chars: &Vec<char>,
+    exp_len: usize,
+    i: usize,
+    evaluator: &Evaluator,
+) -> Option<(Token, usize)> {
+    let start = (i..exp_len).find(|j| chars[*j] != ' ')?;
+    evaluator.unit_register().iter()
+        .filter_map(|unit| {
+            let symbol: Vec<char> = unit.symbol.chars().collect();
+            let end = start + symbol.len();
+            if end <= exp_len && chars[start..end] == symbol[..]
+                && (end == exp_len || !chars[end].is_alphanumeric()) {
+                Some((Token::Unit(unit), end - i))
+            } else {
+                None
+            }
+        })
+        .max_by_key(|(_, consumed)| *consumed)
+}
+
+/// Variables, like those set in a worksheet, are replaced by their values. Like constants, a
+/// variable name must not run on from or into other letters or digits.
+fn parse_variables(
+    chars: &Vec<char>,
+    exp_len: usize,
+    i: usize,
+    evaluator: &Evaluator,
+) -> Option<(Token, usize)> {
+    if i > 0 && chars[i - 1].is_alphanumeric() {
+        return None;
+    }
+    evaluator.variables().iter()
+        .filter_map(|(name, value)| {
+            let name: Vec<char> = name.chars().collect();
+            let end = i + name.len();
+            if end <= exp_len && chars[i..end] == name[..]
+                && (end == exp_len || !chars[end].is_alphanumeric()) {
+                Some((Token::Number(*value), name.len()))
+            } else {
+                None
+            }
+        })
+        .next()
+}
+
 fn parse_constants(
     chars: &Vec<char>,
     exp_len: usize,
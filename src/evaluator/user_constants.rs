@@ -0,0 +1,217 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use std::borrow::Cow;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::evaluator::constants::{self, Constant};
+use crate::evaluator::functions;
+use crate::paths;
+
+static USER_CONSTANTS_FILE: &str = "rusty-calc-constants.json";
+
+static USER_CONSTANT_MANAGER: LazyLock<UserConstantManager> = LazyLock::new(|| -> UserConstantManager {
+    let constants = match load_from_file(get_user_constants_path()) {
+        Ok(saved) => UserConstants::from_saved(saved),
+        Err(e) => {
+            warn!("Unable to open user constants file: {}", e);
+            info!("A new user constants file will be created");
+            UserConstants::default()
+        }
+    };
+
+    UserConstantManager { constants }
+});
+
+/// Constants the user has named, e.g. `myrate = 0.07`. They are used in expressions just like
+/// the built in constants.
+pub struct UserConstantManager {
+    constants: UserConstants,
+}
+
+impl UserConstantManager {
+    fn save_to_file(&self, path: PathBuf) -> io::Result<()> {
+        let serialized = serde_json::to_string(&self.constants.to_saved())?;
+        let mut file = File::create(&path)?;
+        file.write_all(serialized.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = self.save_to_file(get_user_constants_path()) {
+            warn!("{}", e.to_string());
+            warn!("Failed to write user constants.")
+        }
+    }
+
+    pub fn add(&self, name: &str, value: f64) -> Result<(), String> {
+        self.constants.add(name, value)
+    }
+
+    pub fn remove(&self, name: &str) {
+        self.constants.remove(name);
+    }
+
+    pub fn constants(&self) -> Vec<Constant> {
+        self.constants.entries()
+    }
+}
+
+pub fn manager() -> &'static UserConstantManager {
+    &USER_CONSTANT_MANAGER
+}
+
+fn get_user_constants_path() -> PathBuf {
+    paths::data_file(USER_CONSTANTS_FILE)
+}
+
+fn load_from_file(path: PathBuf) -> io::Result<Vec<SavedConstant>> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// How a user constant is kept in the file.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+struct SavedConstant {
+    name: String,
+    value: f64,
+}
+
+#[derive(Debug, Default)]
+struct UserConstants {
+    entries: RwLock<Vec<Constant>>,
+}
+
+impl UserConstants {
+    fn from_saved(saved: Vec<SavedConstant>) -> Self {
+        let constants = Self::default();
+        for c in saved {
+            if let Err(e) = constants.add(&c.name, c.value) {
+                warn!("Ignoring user constant: {}", e);
+            }
+        }
+        constants
+    }
+
+    fn to_saved(&self) -> Vec<SavedConstant> {
+        self.entries().iter()
+            .map(|c| SavedConstant { name: c.name().to_string(), value: c.value })
+            .collect()
+    }
+
+    /// Add a constant, or change the value of an existing user constant. The name can't be
+    /// that of a built in constant or a function, as it could never be used.
+    fn add(&self, name: &str, value: f64) -> Result<(), String> {
+        let name = name.trim();
+        let mut chars = name.chars();
+        if !chars.next().is_some_and(char::is_alphabetic) || !chars.all(char::is_alphanumeric) {
+            return Err(String::from("Constant names must start with a letter and contain only letters and digits"));
+        }
+        if constants::get_builtin().iter().any(|c| c.name() == name) {
+            return Err(format!("'{}' is a built in constant", name));
+        }
+        if functions::get_all().iter().any(|f| f.name() == name)
+            || functions::get_all_multi().iter().any(|f| f.name() == name) {
+            return Err(format!("'{}' is a function", name));
+        }
+        if !value.is_finite() {
+            return Err(String::from("Constants must have a finite value"));
+        }
+        match self.entries.write() {
+            Ok(mut vec) => {
+                match vec.iter_mut().find(|c| c.name() == name) {
+                    Some(existing) => existing.value = value,
+                    None => vec.push(Constant {
+                        name: Cow::Owned(name.to_string()),
+                        long_name: Cow::Owned(name.to_string()),
+                        value,
+                        units: "",
+                    }),
+                }
+                Ok(())
+            }
+            Err(_) => Err(String::from("Failed to write user constants")),
+        }
+    }
+
+    fn remove(&self, name: &str) {
+        match self.entries.write() {
+            Ok(mut vec) => vec.retain(|c| c.name() != name),
+            Err(_) => warn!("Failed to write user constants."),
+        }
+    }
+
+    fn entries(&self) -> Vec<Constant> {
+        self.entries.read().map(|vec| vec.clone()).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::evaluator::user_constants::{load_from_file, SavedConstant, UserConstantManager, UserConstants};
+
+    #[test]
+    fn test_add_remove() {
+        let constants = UserConstants::default();
+        assert!(constants.add("myrate", 0.07).is_ok());
+        assert!(constants.add("g0", 9.80665).is_ok());
+        assert!(constants.add("myrate", 0.08).is_ok());
+        let entries = constants.entries();
+        let values: Vec<(&str, f64)> = entries.iter().map(|c| (c.name(), c.value)).collect();
+        assert_eq!(values, vec![("myrate", 0.08), ("g0", 9.80665)]);
+
+        constants.remove("myrate");
+        assert_eq!(constants.entries().len(), 1);
+    }
+
+    #[test]
+    fn test_name_collisions() {
+        let constants = UserConstants::default();
+        assert_eq!(constants.add("π", 3.0), Err("'π' is a built in constant".to_string()));
+        assert_eq!(constants.add("C", 3.0), Err("'C' is a built in constant".to_string()));
+        assert_eq!(constants.add("sin", 3.0), Err("'sin' is a function".to_string()));
+        assert_eq!(constants.add("max", 3.0), Err("'max' is a function".to_string()));
+        assert!(constants.add("2x", 3.0).is_err());
+        assert!(constants.add("my rate", 3.0).is_err());
+        assert!(constants.entries().is_empty());
+    }
+
+    #[test]
+    fn test_persist() {
+        let manager = UserConstantManager { constants: UserConstants::default() };
+        manager.add("myrate", 0.07).unwrap();
+
+        let path = std::env::temp_dir().join("rusty-calc-constants-test.json");
+        manager.save_to_file(path.clone()).unwrap();
+        let loaded = load_from_file(path.clone()).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(loaded, vec![SavedConstant { name: "myrate".to_string(), value: 0.07 }]);
+    }
+}
@@ -25,33 +25,34 @@ use std::fs::File;
 use std::io::{self, Read, Write};
 use std::path::PathBuf;
 use std::sync::{LazyLock, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use log::{info, warn};
 use serde::{Deserialize, Serialize};
 
+use crate::paths;
+
 static HISTORY_FILE: &str = "rusty-calc-history.json";
 static HISTORY_SIZE: usize = 100;
+/// The largest history file we are prepared to write. Older entries are dropped to fit.
+static HISTORY_MAX_BYTES: usize = 256 * 1024;
 
 static HISTORY_MANAGER: LazyLock<HistoryManager> = LazyLock::new(|| -> HistoryManager{
         let mut contents = String::new();
-        let history = if let Some(path) = get_history_path() {
-            match File::open(path)
-                .and_then(|mut f| {
-                    f.read_to_string(&mut contents)
-                })
-                {
-                    Ok(_s) => {
-                        serde_json::from_str(&contents).unwrap_or(History::new(HISTORY_SIZE))
-                    }
-                    Err(e) => {
-                        warn!("Unable to open history file: {}", e);
-                        info!("A new history file will be created");
-                        History::new(HISTORY_SIZE)
-                    }
+        let history = match File::open(get_history_path())
+            .and_then(|mut f| {
+                f.read_to_string(&mut contents)
+            })
+            {
+                Ok(_s) => {
+                    serde_json::from_str(&contents).unwrap_or(History::new(HISTORY_SIZE))
+                }
+                Err(e) => {
+                    warn!("Unable to open history file: {}", e);
+                    info!("A new history file will be created");
+                    History::new(HISTORY_SIZE)
                 }
-        } else {
-            History::new(HISTORY_SIZE)
-        };
+            };
 
         HistoryManager { history }
     });
@@ -63,22 +64,21 @@ pub struct HistoryManager {
 
 impl HistoryManager {
     fn save_to_file(&self, path: PathBuf) -> io::Result<()> {
-        let serialized = serde_json::to_string(&self.history)?;
+        let serialized = self.history.serialize_capped(HISTORY_MAX_BYTES)?;
         let mut file = File::create(&path)?;
         file.write_all(serialized.as_bytes())?;
         Ok(())
     }
 
     pub fn save(&self) {
-        if let Some(path) = get_history_path() {
-            if let Err(e) = self.save_to_file(path) {
-                warn!("{}", e.to_string());
-                warn!("Failed to write history.")
-            }
+        if let Err(e) = self.save_to_file(get_history_path()) {
+            warn!("{}", e.to_string());
+            warn!("Failed to write history.")
         }
     }
-    pub fn add(&self, entry: (&str, &f64)) {
-        self.history.add(entry);
+    /// Add a calculation, with the angle mode it was made in.
+    pub fn add(&self, entry: (&str, &f64), mode: &str) {
+        self.history.add(entry, Some(mode));
     }
 
     pub fn history(&self) -> &History {
@@ -91,14 +91,46 @@ pub fn manager() -> &'static HistoryManager {
     &HISTORY_MANAGER
 }
 
-fn get_history_path() -> Option<PathBuf> {
-    home::home_dir().map( |home_path| {
-        home_path.join(HISTORY_FILE)
-    })
+fn get_history_path() -> PathBuf {
+    paths::data_file(HISTORY_FILE)
+}
+/// A calculation in the history.
+/// Older history files stored entries as an `[expr, value]` pair, these still load, with no timestamp.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HistoryEntry {
+    pub expr: String,
+    pub value: f64,
+    /// When the calculation was made, in seconds since the Unix epoch, or 0 if not known.
+    #[serde(default)]
+    pub timestamp: u64,
+    /// The angle mode the calculation was made in, if known.
+    #[serde(default)]
+    pub mode: Option<String>,
 }
+
+impl HistoryEntry {
+    fn new(expr: &str, value: f64, mode: Option<&str>) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or(0);
+        Self { expr: expr.to_string(), value, timestamp, mode: mode.map(str::to_string) }
+    }
+}
+
+/// A summary of a number of results.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub count: usize,
+    pub sum: f64,
+    pub mean: f64,
+    pub min: f64,
+    pub max: f64,
+}
+
 #[derive(Serialize, Deserialize, Debug)]
 pub struct History {
-    entries: RwLock<VecDeque<(String, f64)>>,
+    entries: RwLock<VecDeque<HistoryEntry>>,
     #[serde(skip_serializing)]
     #[serde(default="History::defaut_size")]
     max_size: usize,
@@ -117,14 +149,13 @@ impl History {
         }
     }
 
-    fn add(&self, entry: (&str, &f64)) {
+    fn add(&self, entry: (&str, &f64), mode: Option<&str>) {
         match self.entries.write() {
             Ok(mut vec) => {
                 while vec.len() >= self.max_size {
                     vec.pop_back();
                 }
-                let new_entry = (entry.0.to_string(), entry.1.clone());
-                vec.push_front(new_entry);
+                vec.push_front(HistoryEntry::new(entry.0, *entry.1, mode));
             }
             Err(_) => {
                 warn!("Failed to write history.")
@@ -132,8 +163,134 @@ impl History {
         }
     }
 
-    pub fn entries(&self) -> &RwLock<VecDeque<(String, f64)>> {
+    pub fn entries(&self) -> &RwLock<VecDeque<HistoryEntry>> {
         &self.entries
     }
 
-}
\ No newline at end of file
+    /// Serialize the history, first dropping the oldest entries if it would be larger than
+    /// `max_bytes`.
+    fn serialize_capped(&self, max_bytes: usize) -> serde_json::Result<String> {
+        let mut serialized = serde_json::to_string(self)?;
+        while serialized.len() > max_bytes {
+            match self.entries.write() {
+                Ok(mut entries) if !entries.is_empty() => {
+                    // Drop entries in proportion to how far over the limit we are
+                    let len = entries.len();
+                    let excess = (len * (serialized.len() - max_bytes)).div_ceil(serialized.len());
+                    entries.truncate(len - excess.clamp(1, len));
+                    warn!("History exceeds {} bytes, dropped {} old entries", max_bytes, len - entries.len());
+                }
+                _ => break,
+            }
+            serialized = serde_json::to_string(self)?;
+        }
+        Ok(serialized)
+    }
+
+    /// Get the entries whose expression contains the filter text, ignoring case.
+    /// An empty filter matches everything.
+    pub fn matching(&self, filter: &str) -> Vec<HistoryEntry> {
+        let filter = filter.to_lowercase();
+        match self.entries.read() {
+            Ok(entries) => entries.iter()
+                .filter(|entry| entry.expr.to_lowercase().contains(&filter))
+                .cloned()
+                .collect(),
+            Err(_) => vec![],
+        }
+    }
+
+    /// Summarise the values of the `n` most recent entries, if there are any.
+    pub fn stats(&self, n: usize) -> Option<Stats> {
+        let entries = self.entries.read().ok()?;
+        let values: Vec<f64> = entries.iter().take(n).map(|entry| entry.value).collect();
+        if values.is_empty() {
+            return None;
+        }
+        let sum: f64 = values.iter().sum();
+        Some(Stats {
+            count: values.len(),
+            sum,
+            mean: sum / values.len() as f64,
+            min: values.iter().copied().fold(f64::INFINITY, f64::min),
+            max: values.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+        })
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::history::{History, HistoryEntry, Stats};
+
+    fn exprs(entries: Vec<HistoryEntry>) -> Vec<String> {
+        entries.into_iter().map(|entry| entry.expr).collect()
+    }
+
+    #[test]
+    fn test_matching() {
+        let history = History::new(10);
+        history.add(("sin(30)", &0.5), None);
+        history.add(("2 + 2", &4.0), None);
+        history.add(("SQRT(16)", &4.0), None);
+        assert_eq!(history.matching("").len(), 3);
+        assert_eq!(exprs(history.matching("sqrt")), vec!["SQRT(16)"]);
+        assert_eq!(exprs(history.matching("Sin")), vec!["sin(30)"]);
+        assert!(history.matching("cos").is_empty());
+    }
+
+    #[test]
+    fn test_stats() {
+        let history = History::new(10);
+        assert_eq!(history.stats(5), None);
+        for (expr, value) in [("1 + 1", 2.0), ("10 - 20", -10.0), ("2 * 3", 6.0), ("8 / 2", 4.0)] {
+            history.add((expr, &value), None);
+        }
+        // Only the most recent are included
+        assert_eq!(history.stats(3), Some(Stats { count: 3, sum: 0.0, mean: 0.0, min: -10.0, max: 6.0 }));
+        assert_eq!(history.stats(10), Some(Stats { count: 4, sum: 2.0, mean: 0.5, min: -10.0, max: 6.0 }));
+        assert_eq!(history.stats(0), None);
+    }
+
+    #[test]
+    fn test_serialize_capped() {
+        let history = History::new(1000);
+        for i in 0..1000 {
+            history.add((&format!("{} + {}", i, i), &(2.0 * i as f64)), None);
+        }
+        let full = serde_json::to_string(&history).unwrap();
+        assert_eq!(history.serialize_capped(full.len()).unwrap(), full);
+
+        let serialized = history.serialize_capped(1024).unwrap();
+        assert!(serialized.len() <= 1024);
+        let entries = history.entries().read().unwrap();
+        assert!(entries.len() > 0 && entries.len() < 1000);
+        // The newest entries are kept
+        assert_eq!(entries[0].expr, "999 + 999");
+        assert_eq!(entries[0].value, 1998.0);
+    }
+
+    #[test]
+    fn test_timestamps() {
+        let history = History::new(10);
+        history.add(("2 + 2", &4.0), Some("Degrees"));
+        let entry = history.entries().read().unwrap()[0].clone();
+        assert!(entry.timestamp > 0);
+        assert_eq!(entry.mode.as_deref(), Some("Degrees"));
+
+        let serialized = serde_json::to_string(&history).unwrap();
+        let loaded: History = serde_json::from_str(&serialized).unwrap();
+        assert_eq!(loaded.entries().read().unwrap()[0], entry);
+    }
+
+    #[test]
+    fn test_load_without_timestamps() {
+        let old = r#"{"entries":[["sin(30)",0.5],["2 + 2",4.0]]}"#;
+        let history: History = serde_json::from_str(old).unwrap();
+        let entries = history.entries().read().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], HistoryEntry { expr: "sin(30)".to_string(), value: 0.5, timestamp: 0, mode: None });
+        assert_eq!(entries[1].expr, "2 + 2");
+        assert_eq!(entries[1].value, 4.0);
+    }
+}
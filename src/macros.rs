@@ -0,0 +1,222 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+static MACROS_FILE: &str = "rusty-calc-macros.json";
+
+static MACRO_MANAGER: LazyLock<MacroManager> = LazyLock::new(|| -> MacroManager {
+    let macros = match load_from_file(get_macros_path()) {
+        Ok(macros) => macros,
+        Err(e) => {
+            warn!("Unable to open macros file: {}", e);
+            info!("A new macros file will be created");
+            Macros::default()
+        }
+    };
+
+    MacroManager { macros }
+});
+
+/// Macros are recorded sequences of key presses and button clicks that can be replayed,
+/// e.g. to repeat a conversion and formatting on each new value.
+pub struct MacroManager {
+    macros: Macros,
+}
+
+impl MacroManager {
+    fn save_to_file(&self, path: PathBuf) -> io::Result<()> {
+        let serialized = serde_json::to_string(&self.macros)?;
+        let mut file = File::create(&path)?;
+        file.write_all(serialized.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = self.save_to_file(get_macros_path()) {
+            warn!("{}", e.to_string());
+            warn!("Failed to write macros.")
+        }
+    }
+
+    /// Add a recording under the first free name of the form `Macro 1`, returning the name.
+    pub fn add(&self, steps: Vec<Step>) -> Option<String> {
+        self.macros.add(steps)
+    }
+
+    pub fn remove(&self, name: &str) {
+        self.macros.remove(name);
+    }
+
+    pub fn macros(&self) -> &Macros {
+        &self.macros
+    }
+}
+
+pub fn manager() -> &'static MacroManager {
+    &MACRO_MANAGER
+}
+
+fn get_macros_path() -> PathBuf {
+    paths::data_file(MACROS_FILE)
+}
+
+fn load_from_file(path: PathBuf) -> io::Result<Macros> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// One recorded action. These mirror the messages that change the expression or result, units
+/// are kept by name so that a macro can be saved.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub enum Step {
+    Char(String),
+    Constant(String),
+    Func(String),
+    BackSpace,
+    MoveLeft,
+    MoveRight,
+    MoveEnd,
+    Clear,
+    Evaluate,
+    Reciprocal,
+    Square,
+    Negate,
+    ToggleMode,
+    Convert(String, String),
+    SwapUnits,
+    InsertConverted,
+    MemoryAdd,
+    MemorySubtract,
+    MemoryRecall,
+    MemoryClear,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Macro {
+    pub name: String,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Macros {
+    entries: RwLock<Vec<Macro>>,
+}
+
+impl Macros {
+
+    fn add(&self, steps: Vec<Step>) -> Option<String> {
+        if steps.is_empty() {
+            return None;
+        }
+        match self.entries.write() {
+            Ok(mut vec) => {
+                let name = (1..)
+                    .map(|n| format!("Macro {}", n))
+                    .find(|name| !vec.iter().any(|m| &m.name == name))?;
+                vec.push(Macro { name: name.clone(), steps });
+                Some(name)
+            }
+            Err(_) => {
+                warn!("Failed to write macros.");
+                None
+            }
+        }
+    }
+
+    fn remove(&self, name: &str) {
+        match self.entries.write() {
+            Ok(mut vec) => {
+                vec.retain(|m| m.name != name);
+            }
+            Err(_) => {
+                warn!("Failed to write macros.")
+            }
+        }
+    }
+
+    /// The steps of the named macro, if there is one.
+    pub fn steps(&self, name: &str) -> Option<Vec<Step>> {
+        self.entries.read().ok()?
+            .iter()
+            .find(|m| m.name == name)
+            .map(|m| m.steps.clone())
+    }
+
+    pub fn entries(&self) -> &RwLock<Vec<Macro>> {
+        &self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::macros::{load_from_file, MacroManager, Macros, Step};
+
+    fn names(macros: &Macros) -> Vec<String> {
+        macros.entries().read().unwrap().iter().map(|m| m.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_add_remove() {
+        let macros = Macros::default();
+        assert_eq!(macros.add(vec![Step::Char("2".to_string()), Step::Evaluate]), Some("Macro 1".to_string()));
+        assert_eq!(macros.add(vec![Step::Negate]), Some("Macro 2".to_string()));
+        assert_eq!(macros.add(vec![]), None);
+        assert_eq!(names(&macros), vec!["Macro 1", "Macro 2"]);
+
+        // Names are reused once free
+        macros.remove("Macro 1");
+        assert_eq!(macros.add(vec![Step::Square]), Some("Macro 1".to_string()));
+        assert_eq!(macros.steps("Macro 1"), Some(vec![Step::Square]));
+        assert_eq!(macros.steps("Macro 3"), None);
+    }
+
+    #[test]
+    fn test_persist() {
+        let manager = MacroManager { macros: Macros::default() };
+        let steps = vec![
+            Step::Clear,
+            Step::Func("sqrt".to_string()),
+            Step::MoveLeft,
+            Step::Char("2".to_string()),
+            Step::Convert("Kilometre".to_string(), "Mile".to_string()),
+            Step::Evaluate,
+        ];
+        manager.add(steps.clone());
+
+        let path = std::env::temp_dir().join("rusty-calc-macros-test.json");
+        manager.save_to_file(path.clone()).unwrap();
+        let loaded = load_from_file(path.clone()).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        assert_eq!(loaded.steps("Macro 1"), Some(steps));
+    }
+}
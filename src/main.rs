@@ -21,6 +21,7 @@
  */
 
 use std::fs::File;
+use std::path::Path;
 
 use iced::settings::Settings;
 use iced::Size;
@@ -29,6 +30,7 @@ use iced_aw::BOOTSTRAP_FONT_BYTES;
 use log::info;
 use simplelog::*;
 
+use crate::evaluator::{AngleMode, Evaluator};
 use crate::ui::calc_window::CalcWindow;
 
 mod evaluator;
@@ -37,6 +39,11 @@ mod evaluator;
 mod test;
 mod ui;
 pub(crate) mod history;
+pub(crate) mod bookmarks;
+pub(crate) mod macros;
+pub(crate) mod recent;
+pub(crate) mod session;
+pub(crate) mod paths;
 pub(crate) mod conversions;
 
 /// Calculate.
@@ -48,6 +55,30 @@ fn main() -> iced::Result {
         std::env::set_var("ICED_BACKEND", "tiny-skia");
     }
 
+    // Command line options that don't need the user interface
+    let args: Vec<String> = std::env::args().collect();
+    if args.get(1).map(String::as_str) == Some("--list-units") {
+        match conversions::list_units(args.get(2).map(String::as_str)) {
+            Ok(listing) => print!("{}", listing),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+    if args.get(1).map(String::as_str) == Some("--run") {
+        let Some(path) = args.get(2) else {
+            eprintln!("Usage: --run <file>");
+            std::process::exit(1);
+        };
+        if let Err(e) = run_file(Path::new(path)) {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+        return Ok(());
+    }
+
     init_logger();
     info!("Calculator started");
 
@@ -76,30 +107,38 @@ fn main() -> iced::Result {
     result
 }
 
+/// Evaluate a worksheet file, printing each line with its result, in the saved angle mode.
+fn run_file(path: &Path) -> Result<(), String> {
+    let mode = ui::preferences::manager().get::<String>(ui::preferences::ANGLE_MODE)
+        .map(|name| AngleMode::get_from_name(&name))
+        .unwrap_or_default();
+    let lines = Evaluator::with_mode(&mode).run_file(path)?;
+    print!("{}", ui::format::worksheet_text(&lines, &ui::format::FormatOptions::from_preferences()));
+    Ok(())
+}
+
 fn init_logger() {
-    if let Some(home_path) = home::home_dir() {
-        let log_path = home_path.join("rusty-calc.log");
-        match File::create(log_path) {
-            Ok(file) => {
-                CombinedLogger::init(vec![
-                    TermLogger::new(
-                        LevelFilter::Warn,
-                        Config::default(),
-                        TerminalMode::Mixed,
-                        ColorChoice::Auto,
-                    ),
-                    WriteLogger::new(
-                        LevelFilter::Info,
-                        Config::default(),
-                        file,
-                    ),
-                ]).unwrap_or_else(|e| {
-                    println!("Unable to initiate logger: {}.", e)
-                });
-                return;
-            }
-            Err(e) => println!("Unable to initiate logger: {}", e)
+    let log_path = paths::data_file("rusty-calc.log");
+    match File::create(log_path) {
+        Ok(file) => {
+            CombinedLogger::init(vec![
+                TermLogger::new(
+                    LevelFilter::Warn,
+                    Config::default(),
+                    TerminalMode::Mixed,
+                    ColorChoice::Auto,
+                ),
+                WriteLogger::new(
+                    LevelFilter::Info,
+                    Config::default(),
+                    file,
+                ),
+            ]).unwrap_or_else(|e| {
+                println!("Unable to initiate logger: {}.", e)
+            });
+            return;
         }
+        Err(e) => println!("Unable to initiate logger: {}", e)
     }
     TermLogger::init(
         LevelFilter::Warn,
@@ -0,0 +1,87 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use std::env;
+use std::path::PathBuf;
+use std::sync::LazyLock;
+
+use log::warn;
+
+/// Where history, bookmarks and the log are kept.
+static DATA_DIR: LazyLock<DataDir> = LazyLock::new(|| {
+    let data_dir = resolve_data_dir(home::home_dir());
+    if data_dir.fallback {
+        warn!("No home directory, files are kept in {}", data_dir.path.display());
+    }
+    data_dir
+});
+
+struct DataDir {
+    path: PathBuf,
+    /// True if there is no home directory and somewhere else is being used
+    fallback: bool,
+}
+
+/// Files are kept in the home directory. Without one they are kept in the current directory,
+/// or failing that the temporary directory, so they are still saved for this session at least.
+fn resolve_data_dir(home: Option<PathBuf>) -> DataDir {
+    match home {
+        Some(path) => DataDir { path, fallback: false },
+        None => DataDir {
+            path: env::current_dir().unwrap_or_else(|_| env::temp_dir()),
+            fallback: true,
+        },
+    }
+}
+
+/// The path to a file kept in the data directory.
+pub(crate) fn data_file(name: &str) -> PathBuf {
+    DATA_DIR.path.join(name)
+}
+
+/// A warning for the user if files are not being kept in the home directory.
+pub(crate) fn fallback_warning() -> Option<String> {
+    if DATA_DIR.fallback {
+        Some(format!("No home directory, saving to {}", DATA_DIR.path.display()))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use crate::paths::resolve_data_dir;
+
+    #[test]
+    fn test_resolve_data_dir() {
+        let home = PathBuf::from("/home/calc");
+        let data_dir = resolve_data_dir(Some(home.clone()));
+        assert_eq!(data_dir.path, home);
+        assert!(!data_dir.fallback);
+
+        let data_dir = resolve_data_dir(None);
+        assert_eq!(data_dir.path, std::env::current_dir().unwrap());
+        assert!(data_dir.fallback);
+    }
+}
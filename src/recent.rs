@@ -0,0 +1,183 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+use std::sync::{LazyLock, RwLock};
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+static RECENT_FILE: &str = "rusty-calc-recent.json";
+/// Only a handful of conversions are kept, this is not a history.
+static RECENT_MAX: usize = 10;
+
+static RECENT_MANAGER: LazyLock<RecentManager> = LazyLock::new(|| -> RecentManager {
+    let recent = match load_from_file(get_recent_path()) {
+        Ok(recent) => recent,
+        Err(e) => {
+            warn!("Unable to open recent conversions file: {}", e);
+            info!("A new recent conversions file will be created");
+            RecentConversions::default()
+        }
+    };
+
+    RecentManager { recent }
+});
+
+/// Recent conversions are kept separately from the calculation history so a conversion can be
+/// repeated with one click. Units are kept by name.
+pub struct RecentManager {
+    recent: RecentConversions,
+}
+
+impl RecentManager {
+    fn save_to_file(&self, path: PathBuf) -> io::Result<()> {
+        let serialized = serde_json::to_string(&self.recent)?;
+        let mut file = File::create(&path)?;
+        file.write_all(serialized.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = self.save_to_file(get_recent_path()) {
+            warn!("{}", e.to_string());
+            warn!("Failed to write recent conversions.")
+        }
+    }
+
+    pub fn add(&self, conversion: Conversion) {
+        self.recent.add(conversion);
+    }
+
+    pub fn recent(&self) -> &RecentConversions {
+        &self.recent
+    }
+}
+
+pub fn manager() -> &'static RecentManager {
+    &RECENT_MANAGER
+}
+
+fn get_recent_path() -> PathBuf {
+    paths::data_file(RECENT_FILE)
+}
+
+fn load_from_file(path: PathBuf) -> io::Result<RecentConversions> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+/// A conversion that has been done, e.g. 12 Mile to Kilometre.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Conversion {
+    pub dimension: String,
+    pub from: String,
+    pub to: String,
+    pub input: f64,
+    pub output: f64,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct RecentConversions {
+    entries: RwLock<VecDeque<Conversion>>,
+}
+
+impl RecentConversions {
+
+    /// Add a conversion as the most recent. Repeating a conversion moves it to the front
+    /// rather than adding it again.
+    fn add(&self, conversion: Conversion) {
+        if !conversion.input.is_finite() || !conversion.output.is_finite() {
+            return;
+        }
+        match self.entries.write() {
+            Ok(mut queue) => {
+                queue.retain(|c| (&c.from, &c.to, c.input) != (&conversion.from, &conversion.to, conversion.input));
+                queue.push_front(conversion);
+                queue.truncate(RECENT_MAX);
+            }
+            Err(_) => {
+                warn!("Failed to write recent conversions.")
+            }
+        }
+    }
+
+    pub fn entries(&self) -> &RwLock<VecDeque<Conversion>> {
+        &self.entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::recent::{Conversion, load_from_file, RECENT_MAX, RecentConversions, RecentManager};
+
+    fn conversion(input: f64) -> Conversion {
+        Conversion {
+            dimension: "Length".to_string(),
+            from: "Mile".to_string(),
+            to: "Kilometre".to_string(),
+            input,
+            output: input * 1.609344,
+        }
+    }
+
+    #[test]
+    fn test_add_dedupe() {
+        let recent = RecentConversions::default();
+        recent.add(conversion(12.0));
+        recent.add(conversion(5.0));
+        recent.add(conversion(12.0));
+        recent.add(conversion(f64::NAN));
+        let inputs: Vec<f64> = recent.entries().read().unwrap().iter().map(|c| c.input).collect();
+        assert_eq!(inputs, vec![12.0, 5.0]);
+
+        for i in 0..20 {
+            recent.add(conversion(i as f64));
+        }
+        let entries = recent.entries().read().unwrap();
+        assert_eq!(entries.len(), RECENT_MAX);
+        assert_eq!(entries[0].input, 19.0);
+    }
+
+    #[test]
+    fn test_persist() {
+        let manager = RecentManager { recent: RecentConversions::default() };
+        manager.add(conversion(12.0));
+        manager.add(conversion(5.0));
+
+        let path = std::env::temp_dir().join("rusty-calc-recent-test.json");
+        manager.save_to_file(path.clone()).unwrap();
+        let loaded = load_from_file(path.clone()).unwrap();
+        let _ = std::fs::remove_file(path);
+
+        let entries = loaded.entries().read().unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0], conversion(5.0));
+        assert_eq!(entries[1], conversion(12.0));
+    }
+}
@@ -0,0 +1,134 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+use std::fs::{self, File};
+use std::io::{self, Read, Write};
+use std::path::PathBuf;
+
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::paths;
+
+static SESSION_FILE: &str = "rusty-calc-session.json";
+/// How often, in seconds, the session is saved unless another interval has been chosen.
+pub static DEFAULT_INTERVAL: u64 = 60;
+
+/// A snapshot of the work in progress, saved periodically so it can be restored if the
+/// calculator doesn't close cleanly. This is separate from the history, which only has
+/// evaluated expressions. Units are kept by name.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct Session {
+    pub expression: String,
+    pub result: Option<Result<f64, String>>,
+    pub angle_mode: String,
+    pub converting: bool,
+    pub convert_from: Option<String>,
+    pub convert_to: Option<String>,
+}
+
+impl Session {
+    fn save_to_file(&self, path: PathBuf) -> io::Result<()> {
+        let serialized = serde_json::to_string(self)?;
+        let mut file = File::create(&path)?;
+        file.write_all(serialized.as_bytes())?;
+        Ok(())
+    }
+
+    pub fn save(&self) {
+        if let Err(e) = self.save_to_file(get_session_path()) {
+            warn!("{}", e.to_string());
+            warn!("Failed to write session.")
+        }
+    }
+}
+
+/// The session left behind by a calculator that didn't close cleanly, if there is one.
+pub fn load() -> Option<Session> {
+    let path = get_session_path();
+    if !path.exists() {
+        return None;
+    }
+    match load_from_file(path) {
+        Ok(session) => {
+            info!("Restoring the previous session");
+            Some(session)
+        }
+        Err(e) => {
+            warn!("Unable to open session file: {}", e);
+            None
+        }
+    }
+}
+
+/// Remove the saved session when the calculator closes normally.
+pub fn discard() {
+    let path = get_session_path();
+    if path.exists() {
+        if let Err(e) = fs::remove_file(path) {
+            warn!("Failed to remove session file: {}", e);
+        }
+    }
+}
+
+fn get_session_path() -> PathBuf {
+    paths::data_file(SESSION_FILE)
+}
+
+fn load_from_file(path: PathBuf) -> io::Result<Session> {
+    let mut contents = String::new();
+    File::open(path)?.read_to_string(&mut contents)?;
+    Ok(serde_json::from_str(&contents)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::session::{load_from_file, Session};
+
+    #[test]
+    fn test_persist() {
+        let sessions = [
+            Session {
+                expression: "12 * sin(30".to_string(),
+                result: Some(Ok(6.0)),
+                angle_mode: "Degrees".to_string(),
+                converting: true,
+                convert_from: Some("Mile".to_string()),
+                convert_to: Some("Kilometre".to_string()),
+            },
+            Session {
+                expression: "1 / 0".to_string(),
+                result: Some(Err("Division by zero".to_string())),
+                angle_mode: "Radians".to_string(),
+                ..Default::default()
+            },
+            Session::default(),
+        ];
+
+        let path = std::env::temp_dir().join("rusty-calc-session-test.json");
+        for session in sessions {
+            session.save_to_file(path.clone()).unwrap();
+            assert_eq!(load_from_file(path.clone()).unwrap(), session);
+        }
+        let _ = std::fs::remove_file(path);
+    }
+}
@@ -22,8 +22,14 @@
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
+    use rust_decimal::Decimal;
+
     use crate::{assert_err, assert_near};
+    use crate::conversions::length::KILOMETRE;
     use crate::evaluator::{AngleMode, Evaluator};
+    use crate::history;
 
     #[test]
     fn test_add_i() {
@@ -55,6 +61,36 @@ mod tests {
         assert_near!(result.unwrap(), 10.0 / 2.0);
     }
 
+    #[test]
+    fn test_unary_plus() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("+5").unwrap(), 5.0);
+        assert_near!(evaluator.evaluate("3*+2").unwrap(), 6.0);
+        assert_near!(evaluator.evaluate("2 - +1").unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_divide_by_zero() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_err!(evaluator.evaluate("1/0"), "Division by zero");
+        assert_err!(evaluator.evaluate("7 // (2 - 2)"), "Division by zero");
+        assert_near!(evaluator.evaluate("0 / 5").unwrap(), 0.0);
+        // % is a percentage, not the remainder, so it can't divide by zero
+        assert_near!(evaluator.evaluate("0%").unwrap(), 0.0);
+        let evaluator = evaluator.with_quantities(true);
+        assert_err!(evaluator.evaluate("5 km / 0 m"), "Division by zero");
+        assert_err!(evaluator.evaluate("1 GB / 0 Mbit/s"), "Division by zero");
+    }
+
+    #[test]
+    fn test_floor_divide() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("7 // 2").unwrap(), 3.0);
+        assert_near!(evaluator.evaluate("-7 // 2").unwrap(), -4.0);
+        assert_near!(evaluator.evaluate("7 / 2").unwrap(), 3.5);
+        assert_near!(evaluator.evaluate("1 + 9 // 2 * 2").unwrap(), 9.0);
+    }
+
     #[test]
     fn test_exponent() {
         let result = Evaluator::with_mode(&AngleMode::Radians).evaluate("2 ^ 3");
@@ -121,20 +157,101 @@ mod tests {
         assert_near!(evaluator.evaluate("sqrt(94)").unwrap(), 94.0f64.sqrt());
         // Was pythagoras right?
         assert_near!(evaluator.evaluate("sqrt(3*3 + 4^2)").unwrap(), 5.0);
-        assert!(evaluator.evaluate("sqrt(-7.456)").unwrap().is_nan());
+        assert_err!(evaluator.evaluate("sqrt(-7.456)"), "sqrt of negative number");
+        assert_near!(evaluator.evaluate("√16").unwrap(), 4.0);
+        assert_near!(evaluator.evaluate("√(9+16)").unwrap(), 5.0);
+        assert_near!(evaluator.evaluate("2 * √9 + 1").unwrap(), 7.0);
+        assert_err!(evaluator.evaluate("√-4"), "sqrt of negative number");
+        assert_err!(evaluator.evaluate("1 + sqrt(-1)"), "sqrt of negative number");
     }
 
     #[test]
     fn test_factorial() {
         let evaluator = Evaluator::with_mode(&AngleMode::Radians);
         assert_near!(evaluator.evaluate("factorial(5)").unwrap(), 120.0f64);
-        assert_near!(evaluator.evaluate("factorial(5.5)").unwrap(), f64::NAN);
+        assert_err!(evaluator.evaluate("factorial(5.5)"), "factorial requires a positive whole number");
+        assert_err!(evaluator.evaluate("factorial(-2)"), "factorial requires a positive whole number");
         assert_near!(evaluator.evaluate("factorial(1)").unwrap(), 1.0f64);
         assert_near!(evaluator.evaluate("factorial(50)").unwrap(), 3.041409e64, 1.0e59);
         assert_near!(evaluator.evaluate("factorial(169)").unwrap(), 4.269068e304, 1.0e300);
         assert_near!(evaluator.evaluate("factorial(300)").unwrap(), f64::INFINITY);
     }
 
+    #[test]
+    fn test_angle_conversion() {
+        for mode in [AngleMode::Degrees, AngleMode::Radians, AngleMode::Gradians] {
+            let evaluator = Evaluator::with_mode(&mode);
+            assert_near!(evaluator.evaluate("torad(180)").unwrap(), std::f64::consts::PI);
+            assert_near!(evaluator.evaluate("todeg(π)").unwrap(), 180.0f64);
+        }
+    }
+
+    #[test]
+    fn test_gamma() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("gamma(5)").unwrap(), 24.0f64);
+        assert_near!(evaluator.evaluate("gamma(5.5)").unwrap(), 52.342778f64);
+        assert_near!(evaluator.evaluate("lgamma(5)").unwrap(), 24f64.ln());
+        assert_err!(evaluator.evaluate("gamma(-2)"), "gamma is undefined for 0 and negative whole numbers");
+    }
+
+    #[test]
+    fn test_combinations() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("ncr(5,2)").unwrap(), 10.0f64);
+        assert_near!(evaluator.evaluate("npr(5, 2)").unwrap(), 20.0f64);
+        assert_near!(evaluator.evaluate("2 * ncr(3 + 2, 1 + 1)").unwrap(), 20.0f64);
+        assert_err!(evaluator.evaluate("ncr(2, 5)"), "ncr requires 0 <= r <= n");
+        assert_err!(evaluator.evaluate("ncr(1e17, 1e16)"), "ncr requires n <= 2^53");
+        assert_eq!(evaluator.evaluate("ncr(1e12, 5e11)"), Ok(f64::INFINITY));
+        assert_err!(evaluator.evaluate("npr(5.5, 2)"), "npr requires whole numbers");
+        assert_err!(evaluator.evaluate("ncr(5)"), "ncr takes 2 arguments");
+        assert_err!(evaluator.evaluate("ncr(5, 2"), "Unmatched opening parenthesis");
+        assert_eq!(evaluator.to_latex("ncr(5, 2)").unwrap(), "\\binom{5}{2}");
+    }
+
+    #[test]
+    fn test_gcd_lcm() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("gcd(12, 18)").unwrap(), 6.0f64);
+        assert_near!(evaluator.evaluate("lcm(4, 6)").unwrap(), 12.0f64);
+        assert_near!(evaluator.evaluate("gcd(0, 5)").unwrap(), 5.0f64);
+        assert_err!(evaluator.evaluate("gcd(1.5, 3)"), "gcd requires whole numbers");
+    }
+
+    #[test]
+    fn test_rand() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        // Each evaluation is saved in history, the generator itself is sampled more in its own tests
+        for _ in 0..20 {
+            let v = evaluator.evaluate("rand()").unwrap();
+            assert!((0.0..1.0).contains(&v));
+        }
+        let v = evaluator.evaluate("rand(5, 10)").unwrap();
+        assert!((5.0..10.0).contains(&v));
+        assert_err!(evaluator.evaluate("rand(5)"), "rand takes no arguments, or a range");
+    }
+
+    #[test]
+    fn test_hypot() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("hypot(3, 4)").unwrap(), 5.0f64);
+        // Squaring these would overflow
+        assert_near!(evaluator.evaluate("hypot(3e200, 4e200)").unwrap(), 5e200f64, 1e190);
+    }
+
+    #[test]
+    fn test_min_max() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("min(3,1,2)").unwrap(), 1.0f64);
+        assert_near!(evaluator.evaluate("max(3,1,2)").unwrap(), 3.0f64);
+        assert_near!(evaluator.evaluate("max(-1, -5)").unwrap(), -1.0f64);
+        assert_near!(evaluator.evaluate("clamp(5,0,3)").unwrap(), 3.0f64);
+        assert_near!(evaluator.evaluate("clamp(-1,0,3)").unwrap(), 0.0f64);
+        assert_err!(evaluator.evaluate("min(3)"), "min takes at least 2 arguments");
+        assert_err!(evaluator.evaluate("clamp(5,3,0)"), "clamp requires lo <= hi");
+    }
+
     #[test]
     fn test_invalid() {
         let evaluator = Evaluator::with_mode(&AngleMode::Radians);
@@ -155,4 +272,297 @@ mod tests {
             "Invalid token 'p' at position: 0"
         );
     }
+
+    #[test]
+    fn test_is_complete() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert!(evaluator.is_complete("sin(30)"));
+        assert!(evaluator.is_complete("(2 + 3) * 4"));
+        assert!(!evaluator.is_complete("sin(30)+"));
+        assert!(!evaluator.is_complete("sin(30"));
+        assert!(!evaluator.is_complete(""));
+    }
+
+    #[test]
+    fn test_trailing_input() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Degrees);
+        assert!(!evaluator.is_complete("sin(30) 5"));
+        assert!(evaluator.evaluate("sin(30) 5").is_err());
+        assert_eq!(evaluator.evaluate("1 + 2)"), Err(String::from("Unmatched closing parenthesis")));
+    }
+
+    #[test]
+    fn test_quantities() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians).with_quantities(true);
+        let result = evaluator.evaluate_quantity("5 km + 500 m").unwrap();
+        assert_near!(result.value, 5.5);
+        assert_eq!(result.unit.map(|u| u.name), Some("Kilometre"));
+        let result = evaluator.evaluate_quantity("2 * 3 ft / 4").unwrap();
+        assert_near!(result.value, 1.5);
+        assert_eq!(result.unit.map(|u| u.name), Some("Foot"));
+        let result = evaluator.evaluate_quantity("1 km / 500 m").unwrap();
+        assert_near!(result.value, 2.0);
+        assert_eq!(result.unit, None);
+        assert_near!(evaluator.evaluate("2 + 2").unwrap(), 4.0);
+        let result = evaluator.evaluate_quantity("1 GB / 100 Mbit/s").unwrap();
+        assert_near!(result.value, 80.0);
+        assert_eq!(result.unit.map(|u| u.symbol), Some("s"));
+        assert_err!(evaluator.evaluate("5 km + 2 kg"), "Cannot add or subtract Length and Mass");
+        assert_err!(evaluator.evaluate("5 km + 2"), "Cannot add or subtract a plain number and a quantity");
+        assert_err!(
+            evaluator.evaluate("sqrt(4 m)"),
+            "Expected a plain number, but found a quantity in Metre"
+        );
+    }
+
+    #[test]
+    fn test_physical_constants() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("Nₐ / 1e23").unwrap(), 6.02214076);
+        assert_near!(evaluator.evaluate("kʙ * 1e23").unwrap(), 1.380649);
+        assert_near!(evaluator.evaluate("qₑ * 1e19").unwrap(), 1.602176634);
+        assert_near!(evaluator.evaluate("ɸ^2 - ɸ").unwrap(), 1.0);
+    }
+
+    #[test]
+    fn test_single_letter_constants() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("C").unwrap(), 299792458.0);
+        assert_near!(evaluator.evaluate("2 * C").unwrap(), 2.0 * 299792458.0);
+        assert!(evaluator.evaluate("Cat").is_err());
+        assert!(evaluator.evaluate("aC").is_err());
+    }
+
+    #[test]
+    fn test_durations() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians).with_durations(true);
+        assert_near!(evaluator.evaluate("1:30 + 0:45").unwrap(), 2.0 * 3600.0 + 15.0 * 60.0);
+        assert_near!(evaluator.evaluate("1:30:00 + 0:45:00").unwrap(), 2.0 * 3600.0 + 15.0 * 60.0);
+        assert_near!(evaluator.evaluate("0:01:30 * 2").unwrap(), 180.0);
+        assert!(evaluator.evaluate("1: + 2").is_err());
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert!(evaluator.evaluate("1:30 + 0:45").is_err());
+    }
+
+    #[test]
+    fn test_decimal_comma() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians).with_decimal_comma(true);
+        assert_near!(evaluator.evaluate("3,14").unwrap(), 3.14);
+        assert_near!(evaluator.evaluate("1,5 * 2").unwrap(), 3.0);
+        assert_near!(evaluator.evaluate("max(1,5; 2,5)").unwrap(), 2.5);
+        assert_near!(evaluator.evaluate("max(1, 2)").unwrap(), 2.0);
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("max(1,5)").unwrap(), 5.0);
+        assert_near!(evaluator.evaluate("max(1;5)").unwrap(), 5.0);
+    }
+
+    #[test]
+    fn test_wavelength() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("wavelength(100e6)").unwrap(), 2.99792458);
+        assert_near!(evaluator.evaluate("frequency(2.99792458)").unwrap(), 100e6);
+        assert_eq!(evaluator.evaluate("wavelength(0)"), Err("wavelength requires a positive value".to_string()));
+    }
+
+    #[test]
+    fn test_haversine() {
+        // Latitudes and longitudes are always in degrees
+        for mode in [AngleMode::Radians, AngleMode::Degrees] {
+            let evaluator = Evaluator::with_mode(&mode);
+            assert_near!(evaluator.evaluate("haversine(51.5074, -0.1278, 48.8566, 2.3522)").unwrap(), 343.556, 0.001);
+        }
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians).with_quantities(true);
+        let result = evaluator.evaluate_quantity("haversine(51.5074, -0.1278, 48.8566, 2.3522)").unwrap();
+        assert_eq!(result.unit, Some(&KILOMETRE));
+        assert_near!(evaluator.evaluate("haversine(51.5074, -0.1278, 48.8566, 2.3522) in mi").unwrap(), 213.476, 0.001);
+        assert_near!(evaluator.evaluate("haversine(51.5074, -0.1278, 48.8566, 2.3522) / 2").unwrap(), 171.778, 0.001);
+        assert!(evaluator.evaluate("haversine(1, 2, 3)").is_err());
+    }
+
+    #[test]
+    fn test_tip() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Degrees);
+        assert_near!(evaluator.evaluate("tip(50, 18)").unwrap(), 9.0);
+        assert_near!(evaluator.evaluate("total_with_tip(50, 18)").unwrap(), 59.0);
+        assert_near!(evaluator.evaluate("total_with_tip(80, 12.5) / 4").unwrap(), 22.5);
+        assert!(evaluator.evaluate("tip(50)").is_err());
+    }
+
+    #[test]
+    fn test_run_script() {
+        let script = "# A loan\nprincipal = 1000\nrate = 0.05\n\nprincipal * rate\nans * 2\nsin = 3\nrate4 = rate * 4\nprincipal + rate4\nnothing + 1";
+        let lines = Evaluator::with_mode(&AngleMode::Radians).run_script(script);
+        let results: Vec<(&str, Result<f64, String>)> = lines.iter()
+            .map(|line| (line.line.as_str(), line.result.clone()))
+            .collect();
+        assert_eq!(results, vec![
+            ("principal = 1000", Ok(1000.0)),
+            ("rate = 0.05", Ok(0.05)),
+            ("principal * rate", Ok(50.0)),
+            ("ans * 2", Ok(100.0)),
+            ("sin = 3", Err("'sin' is a function".to_string())),
+            ("rate4 = rate * 4", Ok(0.2)),
+            ("principal + rate4", Ok(1000.2)),
+            ("nothing + 1", Err("Invalid token 'n' at position: 0".to_string())),
+        ]);
+    }
+
+    #[test]
+    fn test_integers() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians).with_integers(true);
+        assert_eq!(evaluator.evaluate("7 / 2").unwrap(), 3.0);
+        assert_eq!(evaluator.evaluate("3 * 2 + 1").unwrap(), 7.0);
+        assert_eq!(evaluator.evaluate("-7 / 2").unwrap(), -3.0);
+        assert_eq!(evaluator.evaluate("7 / 2 * 2").unwrap(), 6.0);
+        assert_eq!(evaluator.evaluate("sqrt(10) * 2").unwrap(), 6.0);
+        assert_eq!(evaluator.evaluate("50 + 10%").unwrap(), 55.0);
+        assert_eq!(evaluator.evaluate("50 - 10%").unwrap(), 45.0);
+        assert_eq!(evaluator.evaluate("7 / 2 + 10%").unwrap(), 3.0);
+    }
+
+    #[test]
+    fn test_plausible_prefix() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert!(evaluator.is_plausible_prefix(""));
+        assert!(evaluator.is_plausible_prefix("2 * si"));
+        assert!(evaluator.is_plausible_prefix("sin(π/2) + log"));
+        assert!(evaluator.is_plausible_prefix("1.5e3 + "));
+        assert!(!evaluator.is_plausible_prefix("qz"));
+        assert!(!evaluator.is_plausible_prefix("2 + sinx"));
+        assert!(!evaluator.is_plausible_prefix("5 j"));
+        assert!(evaluator.is_plausible_prefix("total_with"));
+        assert!(evaluator.is_plausible_prefix("total_with_tip(50, 18)"));
+        assert!(!evaluator.is_plausible_prefix("total_without"));
+        let evaluator = evaluator.with_quantities(true);
+        assert!(evaluator.is_plausible_prefix("5 k"));
+        assert!(evaluator.is_plausible_prefix("5 km i"));
+    }
+
+    #[test]
+    fn test_quantities_off() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_err!(evaluator.evaluate("5 km"), "Invalid token 'k' at position: 2");
+    }
+
+    #[test]
+    fn test_decimals() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians).with_decimals(true);
+        let result = evaluator.evaluate_quantity("0.1 + 0.2").unwrap();
+        assert_eq!(result.exact, Some(Decimal::from_str("0.3").unwrap()));
+        assert_eq!(result.value, 0.3);
+        assert!(!result.approximate);
+        assert!(evaluator.evaluate_quantity("sqrt(2)").unwrap().approximate);
+        assert!(evaluator.evaluate_quantity("2 ^ 0.5").unwrap().approximate);
+        assert!(evaluator.evaluate_quantity("max(1, 2) + 1").unwrap().approximate);
+        assert!(!evaluator.evaluate_quantity("2 ^ -2").unwrap().approximate);
+        assert_eq!(evaluator.evaluate("1.1 * 1.1").unwrap(), 1.21);
+        assert_eq!(evaluator.evaluate("10 / 4 - 0.5").unwrap(), 2.0);
+        assert_eq!(evaluator.evaluate("1.5 ^ 2").unwrap(), 2.25);
+        assert_eq!(evaluator.evaluate("2 ^ -2").unwrap(), 0.25);
+        assert_near!(evaluator.evaluate("sqrt(2)").unwrap(), 2.0f64.sqrt());
+        assert_err!(evaluator.evaluate("1 / 0"), "Division by zero");
+        // Floating point gets it wrong
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_ne!(evaluator.evaluate("0.1 + 0.2").unwrap(), 0.3);
+    }
+
+    #[test]
+    fn test_conversion_targets() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians).with_quantities(true);
+        let result = evaluator.evaluate_quantity("1.83 m in ft+in").unwrap();
+        assert_eq!(result.compound_display(), Some("6 ft 0 in".to_string()));
+        let result = evaluator.evaluate_quantity("1.75 m in ft in").unwrap();
+        assert_eq!(result.compound_display(), Some("5 ft 9 in".to_string()));
+        let result = evaluator.evaluate_quantity("2 ft + 6 in to in").unwrap();
+        assert_near!(result.value, 30.0);
+        assert_eq!(result.unit.map(|u| u.symbol), Some("in"));
+        assert_eq!(result.compound_display(), None);
+        assert!(evaluator.is_complete("1.83 m in ft+in"));
+
+        assert_err!(evaluator.evaluate("1.83 m in ft+xyz"), "Unknown unit 'xyz'");
+        assert_err!(evaluator.evaluate("1.83 m in in+ft"), "Compound units must be given largest first");
+        assert_err!(evaluator.evaluate("1.83 m in ft+kg"), "Cannot convert Length to Mass");
+        assert_err!(evaluator.evaluate("1.83 in ft"), "Only quantities with units can be converted");
+    }
+
+    #[test]
+    fn test_history_skips_nan() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        let recorded = |expr: &str| history::manager().history().matching(expr)
+            .iter()
+            .any(|entry| entry.expr == expr);
+        assert!(evaluator.evaluate("10 ^ 400 - 10 ^ 400 + 1234.5").unwrap().is_nan());
+        assert!(!recorded("10 ^ 400 - 10 ^ 400 + 1234.5"));
+        assert!(evaluator.evaluate("sqrt(-1) + 1234.5").is_err());
+        assert!(!recorded("sqrt(-1) + 1234.5"));
+        assert!(evaluator.evaluate("10 ^ 400 + 1234.5").unwrap().is_infinite());
+        assert!(!recorded("10 ^ 400 + 1234.5"));
+        assert_near!(evaluator.evaluate("1 / 8 + 1234.5").unwrap(), 1234.625);
+        assert!(recorded("1 / 8 + 1234.5"));
+    }
+
+    #[test]
+    fn test_preview_skips_history() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        let recorded = |expr: &str| history::manager().history().matching(expr)
+            .iter()
+            .any(|entry| entry.expr == expr);
+        assert_near!(evaluator.evaluate_preview("3 / 8 + 4321.5").unwrap().value, 4321.875);
+        assert!(!recorded("3 / 8 + 4321.5"));
+        assert_err!(evaluator.evaluate_preview(""), "Please supply an expression to evaluate");
+    }
+
+    #[test]
+    fn test_dms() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Degrees);
+        assert_near!(evaluator.evaluate("12°30'").unwrap(), 12.5);
+        assert_near!(evaluator.evaluate("12°30'15\"").unwrap(), 12.504166667);
+        assert_near!(evaluator.evaluate("45°").unwrap(), 45.0);
+        assert_near!(evaluator.evaluate("-12°30' + 1").unwrap(), -11.5);
+        assert_near!(evaluator.evaluate("sin(30°)").unwrap(), 0.5);
+        // Minutes must be marked, rather than the 30 being dropped
+        assert!(evaluator.evaluate("12°30").is_err());
+        let evaluator = Evaluator::with_mode(&AngleMode::Degrees).with_quantities(true);
+        assert_eq!(evaluator.evaluate_quantity("20°C").unwrap().unit.map(|u| u.symbol), Some("°C"));
+    }
+
+    #[test]
+    fn test_latex() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Degrees);
+        assert_eq!(evaluator.to_latex("3 + 4 * 2").unwrap(), "3 + 4 \\times 2");
+        assert_eq!(evaluator.to_latex("(3 + 4) * 2").unwrap(), "\\left(3 + 4\\right) \\times 2");
+        assert_eq!(evaluator.to_latex("1 / (2 + 3)").unwrap(), "\\frac{1}{2 + 3}");
+        assert_eq!(evaluator.to_latex("(1 + 2) ^ 2").unwrap(), "\\left(1 + 2\\right)^{2}");
+        assert_eq!(evaluator.to_latex("5 - (2 - 1)").unwrap(), "5 - \\left(2 - 1\\right)");
+        assert_eq!(evaluator.to_latex("sqrt(16) + sin(30)").unwrap(), "\\sqrt{16} + \\sin\\left(30\\right)");
+        assert_eq!(evaluator.to_latex("-(2 + 1)").unwrap(), "-\\left(2 + 1\\right)");
+        assert!(evaluator.to_latex("3 +").is_err());
+    }
+
+    #[test]
+    fn test_scientific_input() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_eq!(evaluator.evaluate("1.5e3").unwrap(), 1500.0);
+        assert_eq!(evaluator.evaluate("2E-4").unwrap(), 0.0002);
+        assert_eq!(evaluator.evaluate("2e+2 * 3").unwrap(), 600.0);
+        assert_near!(evaluator.evaluate("2 * ℇ").unwrap(), 2.0 * std::f64::consts::E);
+        assert_near!(evaluator.evaluate("exp(1)").unwrap(), std::f64::consts::E);
+        assert_err!(evaluator.evaluate("2e"), "Invalid token 'e' at position: 1");
+        assert_err!(evaluator.evaluate("2e-"), "Invalid token 'e' at position: 1");
+    }
+
+    #[test]
+    fn test_percent() {
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians);
+        assert_near!(evaluator.evaluate("200*15%").unwrap(), 30.0);
+        assert_near!(evaluator.evaluate("50+10%").unwrap(), 55.0);
+        assert_near!(evaluator.evaluate("50-10%").unwrap(), 45.0);
+        assert_near!(evaluator.evaluate("25%").unwrap(), 0.25);
+        assert_near!(evaluator.evaluate("(50+10)%").unwrap(), 0.6);
+        assert_near!(evaluator.evaluate("2 * 3 + 50%").unwrap(), 9.0);
+        assert_eq!(evaluator.to_latex("50 + 10%").unwrap(), "50 + 10\\%");
+        assert_eq!(evaluator.to_latex("(50 - 10%) * 2").unwrap(), "\\left(50 - 10\\%\\right) \\times 2");
+        let evaluator = Evaluator::with_mode(&AngleMode::Radians).with_decimals(true);
+        assert_eq!(evaluator.evaluate("19.99 + 10%").unwrap(), 21.989);
+    }
 }
@@ -22,22 +22,40 @@
 
 // This is the main ICED UI Application.
 
-use iced::{Background, Border, Color, Degrees, Element, event, Event, gradient, Length, Padding, Pixels, Radians, Renderer, Shadow, Subscription, Task, Theme, Vector, window};
+use std::sync::Arc;
+use std::time::Duration;
+
+use iced::{Background, Border, Color, Degrees, Element, event, Event, gradient, keyboard, Length, Padding, Pixels, Radians, Renderer, Shadow, Subscription, Task, Theme, time, Vector, window};
 use iced::clipboard;
+use iced::keyboard::{Key, Modifiers};
+use iced::keyboard::key::Named;
 use iced::alignment::{Horizontal, Vertical};
 use iced::theme::palette::Pair;
-use iced::widget::{Button, button, Column, container, Container, horizontal_rule, Row, rule, text, Text, text_editor, tooltip};
+use iced::widget::{Button, button, Column, container, Container, focus_next, focus_previous, horizontal_rule, Row, rule, scrollable, text, Text, text_editor, text_input, tooltip};
 use iced::widget::button::Status;
-use iced::widget::text_editor::{Action, Content, Edit, Motion};
+use iced::widget::text_editor::{Action, Binding, Content, Edit, KeyPress, Motion};
 use iced::widget::tooltip::Position;
+use iced_aw::{Bootstrap, BOOTSTRAP_FONT};
+use iced_aw::bootstrap::icon_to_string;
 use log::warn;
 use palette::{convert::FromColor, Hsl};
 use palette::rgb::Rgb;
 
-use crate::conversions::{try_convert, Unit};
-use crate::evaluator::AngleMode;
+use crate::{bookmarks, history, macros, paths, recent, session};
+use crate::conversions::{convert, find_unit, round_significant, try_convert, Unit};
+use crate::conversions::angle::in_other_modes;
+use crate::conversions::length::{DEFAULT_DPI, KILOMETRE, MILE, set_dpi};
+use crate::conversions::slope::{Slope, SlopeField};
+use crate::evaluator::{AngleMode, constants, user_constants};
+use crate::evaluator::constants::{C, Constant, Euler, G, Pi};
+use crate::evaluator::quantity::Quantity;
+use crate::evaluator::script::ScriptLine;
+use crate::macros::Step;
+use crate::recent::Conversion;
+use crate::session::Session;
 use crate::ui;
 use crate::ui::calculator::Calc;
+use crate::ui::format::{as_pi_multiple, constant_text, conversion_sentence, copy_text, describe_expression_and_result, display_converted, display_result, format_dms, FormatOptions, format_duration, history_metadata, insertion_text, number_to_words, result_expression, result_line, worksheet_text};
 use crate::ui::menu::build_menu_bar;
 use crate::ui::messages::Message;
 
@@ -46,10 +64,32 @@ pub(crate) struct CalcWindow {
     theme: Theme,
     content: Content,
     result: Option<Result<f64, String>>,
+    /// The unit or exact decimal value of the result, when it has one
+    result_quantity: Option<Quantity>,
+    result_index: Option<usize>,
+    /// The expression that gave the result
+    result_expr: Option<String>,
+    /// The provisional result of the expression being typed
+    preview: Option<f64>,
     calc: Calc,
     is_converting: bool,
     convert_from: Option<&'static Unit>,
     convert_to: Option<&'static Unit>,
+    pinned: Option<(&'static Unit, &'static Unit)>,
+    /// Show the result as an angle in the units of the other angle modes
+    show_angles: bool,
+    /// The slope tool, when it is open
+    slope: Option<SlopeTool>,
+    /// The results of the worksheet last run, while they are shown
+    worksheet: Option<Vec<ScriptLine>>,
+    /// The steps of the macro being recorded
+    recording: Option<Vec<Step>>,
+    /// The session as last saved, so it is only saved again when something has changed
+    saved_session: Option<Session>,
+    history_filter: String,
+    memory: f64,
+    /// AC has been pressed once on a long expression and must be pressed again to clear it
+    confirm_clear: bool,
     window_width: f32,
     window_height: f32,
     window_x: f32,
@@ -64,16 +104,56 @@ impl Default for CalcWindow {
         if let Some(am) = pref.get::<String>(crate::ui::preferences::ANGLE_MODE) {
             calc.set_angle_mode(AngleMode::get_from_name(am.as_str()));
         }
+        apply_options(&mut calc);
         let theme = theme_by_name(pref.get::<String>(ui::preferences::THEME)).clone();
+        let memory = pref.get::<f64>(ui::preferences::MEMORY).unwrap_or(0.0);
+        let pinned = pref.get::<String>(ui::preferences::PINNED_FROM)
+            .and_then(|name| find_unit(&name))
+            .zip(pref.get::<String>(ui::preferences::PINNED_TO)
+                .and_then(|name| find_unit(&name)));
+        let last_conversion = recent::manager().recent().entries().read().ok()
+            .and_then(|entries| entries.front().map(|c| (c.from.clone(), c.to.clone())));
+        let start_units = start_conversion(
+            pref.get::<bool>(ui::preferences::START_IN_CONVERT).unwrap_or(false),
+            last_conversion.as_ref().map(|(from, to)| (from.as_str(), to.as_str())));
+        let mut content = Content::default();
+        let mut result = None;
+        let mut converting = (start_units.is_some(), start_units.map(|(from, _)| from), start_units.map(|(_, to)| to));
+
+        // Pick up where we left off if the calculator didn't close cleanly
+        let saved_session = session::load();
+        if let Some(s) = &saved_session {
+            content = Content::with_text(&s.expression);
+            content.perform(Action::Move(Motion::DocumentEnd));
+            result = s.result.clone();
+            calc.set_angle_mode(AngleMode::get_from_name(&s.angle_mode));
+            converting = (s.converting,
+                          s.convert_from.as_deref().and_then(find_unit),
+                          s.convert_to.as_deref().and_then(find_unit));
+        }
 
         Self {
             theme: theme,
-            content: Default::default(),
-            result: None,
+            content,
+            // Let the user know once if their work can't be saved in the usual place
+            result: paths::fallback_warning().map(Err).or(result),
+            result_quantity: None,
+            result_index: None,
+            result_expr: None,
+            preview: None,
             calc: calc,
-            is_converting: false,
-            convert_from: None,
-            convert_to: None,
+            is_converting: converting.0,
+            convert_from: converting.1,
+            convert_to: converting.2,
+            pinned,
+            show_angles: false,
+            slope: None,
+            worksheet: None,
+            recording: None,
+            saved_session,
+            history_filter: String::new(),
+            memory,
+            confirm_clear: false,
             window_width: 0.0,
             window_height: 0.0,
             window_x: 0.0,
@@ -88,22 +168,94 @@ impl CalcWindow {
         "Rusty Calculator".to_string()
     }
     pub fn update(&mut self, message: Message) -> Task<Message> {
+        // Saving in the background mustn't interrupt what the user is doing
+        if let Message::AutoSave = message {
+            return self.handle(message, false);
+        }
+        // Waiting for AC to be pressed again only lasts until the next message
+        let confirming = std::mem::take(&mut self.confirm_clear);
+        if let Some(steps) = self.recording.as_mut() {
+            steps.extend(message.to_step());
+        }
+        let task = self.handle(message, confirming);
+        self.update_preview();
+        task
+    }
+
+    fn handle(&mut self, message: Message, confirming: bool) -> Task<Message> {
         match message {
             Message::Char(s) => {
                 for c in s.chars() {
                     self.content.perform(Action::Edit(Edit::Insert(c)));
                 }
+                if s == ")" {
+                    self.auto_evaluate();
+                }
                 Task::none()
             }
             Message::Constant(s) => {
+                let as_value = ui::preferences::manager().get::<bool>(ui::preferences::CONSTANT_VALUES).unwrap_or(false);
+                let s = constants::get_all().iter()
+                    .find(|c| c.name() == s)
+                    .map_or(s, |c| constant_text(c, as_value));
                 for c in s.chars() {
                     self.content.perform(Action::Edit(Edit::Insert(c)));
                 }
                 Task::none()
             }
+            Message::AddConstant => {
+                let text = self.content.text();
+                self.result = Some(match split_definition(text.trim()) {
+                    Some((name, expr)) => self.calc.evaluate(expr)
+                        .and_then(|q| user_constants::manager().add(name, q.value).map(|_| q.value)),
+                    None => Err(String::from("Enter a constant definition such as myrate = 0.07")),
+                });
+                self.result_quantity = None;
+                self.result_expr = None;
+                user_constants::manager().save();
+                Task::none()
+            }
+            Message::RemoveConstant(name) => {
+                user_constants::manager().remove(&name);
+                user_constants::manager().save();
+                Task::none()
+            }
             Message::Copy(v) => {
                 clipboard::write(v.to_string())
             }
+            Message::FocusNext => focus_next(),
+            Message::FocusPrevious => focus_previous(),
+            Message::Paste => {
+                clipboard::read().map(|text| match text {
+                    Some(text) => Message::Char(single_line(&text)),
+                    None => Message::Null,
+                })
+            }
+            Message::CopyExpression(expr) => {
+                // The editor text usually ends with a new line
+                clipboard::write(expr.trim_end().to_string())
+            }
+            Message::CopyAs(format) => {
+                match &self.result {
+                    Some(Ok(v)) => {
+                        let latex = self.calc.to_latex(self.content.text().trim()).ok();
+                        clipboard::write(copy_text(format, *v, latex.as_deref(), &FormatOptions::from_preferences()))
+                    }
+                    _ => Task::none()
+                }
+            }
+            Message::CopyConversionSentence => {
+                match (&self.result, self.convert_from, self.convert_to) {
+                    (Some(Ok(v)), Some(from), Some(to)) => {
+                        // The sentence has the input as it was converted
+                        let figures = input_figures();
+                        let input = figures.map_or(*v, |figures| round_significant(*v, figures));
+                        let output = try_convert(v, &self.convert_from, &self.convert_to, figures);
+                        clipboard::write(conversion_sentence(input, from, output, to, &FormatOptions::from_preferences()))
+                    }
+                    _ => Task::none()
+                }
+            }
             Message::Func(s) => {
                 // If we have a selection, we want to surround it with the function
                 if let Some(sel) = self.content.selection() {
@@ -125,14 +277,34 @@ impl CalcWindow {
                     Task::perform(async {}, |_| Message::MoveLeft)
                 }
             }
+            Message::Reciprocal => {
+                self.wrap_expression(reciprocal);
+                Task::none()
+            }
+            Message::Square => {
+                self.wrap_expression(square);
+                Task::none()
+            }
+            Message::Negate => {
+                self.wrap_expression(negate);
+                Task::none()
+            }
             Message::EditorAction(action) => {
                 match action {
                     Action::Edit(Edit::Enter) => {
-                        self.result = Some(self.calc.evaluate(&self.content.text().trim()));
+                        self.evaluate_expression();
                         Task::perform(async {}, |_| Message::MoveEnd)
                     }
+                    Action::Edit(Edit::Paste(text)) => {
+                        self.content.perform(Action::Edit(Edit::Paste(Arc::new(single_line(&text)))));
+                        Task::none()
+                    }
                     _ => {
+                        let closes_paren = matches!(action, Action::Edit(Edit::Insert(')')));
                         self.content.perform(action);
+                        if closes_paren {
+                            self.auto_evaluate();
+                        }
                         Task::none()
                     }
                 }
@@ -145,14 +317,57 @@ impl CalcWindow {
                     self.content.perform(Action::Edit(Edit::Insert(c)));
                 }
                 self.result = Some(Ok(value));
+                self.result_quantity = None;
+                self.result_expr = Some(expr);
+                self.result_index = None;
+                Task::none()
+            }
+            Message::PreviousResult => {
+                self.step_result(true);
+                Task::none()
+            }
+            Message::NextResult => {
+                self.step_result(false);
+                Task::none()
+            }
+            Message::HistoryFilter(filter) => {
+                self.history_filter = filter;
+                Task::none()
+            }
+            Message::Bookmark(expr) => {
+                self.content.perform(Action::Move(Motion::DocumentStart));
+                self.content.perform(Action::Select(Motion::DocumentEnd));
+                self.content.perform(Action::Edit(Edit::Delete));
+                for c in expr.chars() {
+                    self.content.perform(Action::Edit(Edit::Insert(c)));
+                }
+                self.result = None;
+                self.result_quantity = None;
+                self.result_expr = None;
+                self.result_index = None;
+                Task::none()
+            }
+            Message::AddBookmark => {
+                bookmarks::manager().add(&self.content.text());
+                bookmarks::manager().save();
+                Task::none()
+            }
+            Message::RemoveBookmark(expr) => {
+                bookmarks::manager().remove(&expr);
+                bookmarks::manager().save();
                 Task::none()
             }
 
             Message::Evaluate => {
-                self.result = Some(self.calc.evaluate(&self.content.text().trim()));
+                self.evaluate_expression();
                 Task::none()
             }
             Message::Clear => {
+                let confirm = ui::preferences::manager().get::<bool>(ui::preferences::CONFIRM_CLEAR).unwrap_or(false);
+                if !clear_confirmed(self.content.text().trim().chars().count(), confirming, confirm) {
+                    self.confirm_clear = true;
+                    return Task::none();
+                }
                 self.content.perform(Action::Move(Motion::DocumentStart));
                 self.content.perform(Action::Select(Motion::DocumentEnd));
                 self.content.perform(Action::Edit(Edit::Delete));
@@ -160,6 +375,9 @@ impl CalcWindow {
                 self.convert_from = None;
                 self.convert_to = None;
                 self.result = None;
+                self.result_quantity = None;
+                self.result_expr = None;
+                self.result_index = None;
                 Task::none()
             }
             Message::MoveLeft => {
@@ -188,12 +406,87 @@ impl CalcWindow {
                     Task::none()
                 }
             }
+            Message::MemoryAdd => {
+                self.set_memory(memory_update(self.memory, &self.result, false));
+                Task::none()
+            }
+            Message::MemorySubtract => {
+                self.set_memory(memory_update(self.memory, &self.result, true));
+                Task::none()
+            }
+            Message::MemoryRecall => {
+                for c in self.memory.to_string().chars() {
+                    self.content.perform(Action::Edit(Edit::Insert(c)));
+                }
+                Task::none()
+            }
+            Message::MemoryClear => {
+                self.set_memory(0.0);
+                Task::none()
+            }
+            Message::RepeatConversion(from_unit, to_unit, input) => {
+                self.content.perform(Action::Move(Motion::DocumentStart));
+                self.content.perform(Action::Select(Motion::DocumentEnd));
+                self.content.perform(Action::Edit(Edit::Delete));
+                for c in input.to_string().chars() {
+                    self.content.perform(Action::Edit(Edit::Insert(c)));
+                }
+                self.is_converting = true;
+                self.convert_from = Some(from_unit);
+                self.convert_to = Some(to_unit);
+                self.evaluate_expression();
+                Task::none()
+            }
+            Message::SwapUnits => {
+                if self.is_converting {
+                    std::mem::swap(&mut self.convert_from, &mut self.convert_to);
+                }
+                Task::none()
+            }
+            Message::InsertConverted => {
+                if let (Some(Ok(v)), true) = (&self.result, self.is_converting) {
+                    let cv = try_convert(v, &self.convert_from, &self.convert_to, input_figures());
+                    let quantities = ui::preferences::manager().get::<bool>(ui::preferences::QUANTITIES).unwrap_or(false);
+                    let unit = if quantities { self.convert_to } else { None };
+                    for c in insertion_text(&cv, unit).chars() {
+                        self.content.perform(Action::Edit(Edit::Insert(c)));
+                    }
+                }
+                Task::none()
+            }
+            Message::PinConversion(from_unit, to_unit) => {
+                self.pinned = Some((from_unit, to_unit));
+                let pref = ui::preferences::manager();
+                pref.put(ui::preferences::PINNED_FROM, from_unit.name);
+                pref.put(ui::preferences::PINNED_TO, to_unit.name);
+                Task::none()
+            }
+            Message::UnpinConversion => {
+                self.pinned = None;
+                let pref = ui::preferences::manager();
+                pref.remove(ui::preferences::PINNED_FROM);
+                pref.remove(ui::preferences::PINNED_TO);
+                Task::none()
+            }
             Message::ThemeChanged(t) => {
                 self.theme = t;
                 let pref = ui::preferences::manager();
                 pref.put(ui::preferences::THEME, format!("{}", &self.theme));
                 Task::none()
             }
+            Message::ToggleOption(key) => {
+                let pref = ui::preferences::manager();
+                let value = pref.get::<bool>(key).unwrap_or(false);
+                pref.put(key, !value);
+                apply_options(&mut self.calc);
+                Task::none()
+            }
+            Message::SetOption(key, value) => {
+                let pref = ui::preferences::manager();
+                pref.put(key, value);
+                apply_options(&mut self.calc);
+                Task::none()
+            }
             Message::WindowResized(w, h) => {
                 self.window_width = w.clone();
                 self.window_height = h.clone();
@@ -206,6 +499,106 @@ impl CalcWindow {
             }
             Message::WindowClosed() => {
                 let _ = save_window_size(self.window_width, self.window_height);
+                session::discard();
+                Task::none()
+            }
+            Message::AutoSave => {
+                let session = self.session();
+                if self.saved_session.as_ref() != Some(&session) {
+                    session.save();
+                    self.saved_session = Some(session);
+                }
+                Task::none()
+            }
+            Message::StartRecording => {
+                self.recording = Some(Vec::new());
+                Task::none()
+            }
+            Message::StopRecording => {
+                if let Some(steps) = self.recording.take() {
+                    macros::manager().add(steps);
+                    macros::manager().save();
+                }
+                Task::none()
+            }
+            Message::RunMacro(name) => {
+                let steps = macros::manager().macros().steps(&name).unwrap_or_default();
+                for message in steps.iter().filter_map(Step::to_message) {
+                    // Follow on messages, like moving into the parentheses of a function, were
+                    // recorded as steps of their own so the tasks are not needed
+                    let _ = self.handle(message, true);
+                }
+                Task::none()
+            }
+            Message::RemoveMacro(name) => {
+                macros::manager().remove(&name);
+                macros::manager().save();
+                Task::none()
+            }
+            Message::RunFile => {
+                let pick = rfd::AsyncFileDialog::new()
+                    .set_title("Run file")
+                    .pick_file();
+                Task::perform(pick, |file| match file {
+                    Some(file) => Message::RunWorksheet(file.path().to_path_buf()),
+                    None => Message::Null,
+                })
+            }
+            Message::RunWorksheet(path) => {
+                match self.calc.run_file(&path) {
+                    Ok(lines) => self.worksheet = Some(lines),
+                    Err(e) => {
+                        self.result = Some(Err(e));
+                        self.result_quantity = None;
+                        self.result_expr = None;
+                    }
+                }
+                Task::none()
+            }
+            Message::ExportWorksheet => {
+                let Some(lines) = &self.worksheet else {
+                    return Task::none();
+                };
+                let results = worksheet_text(lines, &FormatOptions::from_preferences());
+                let save = rfd::AsyncFileDialog::new()
+                    .set_title("Export results")
+                    .set_file_name("results.txt")
+                    .save_file();
+                Task::perform(async move {
+                    match save.await {
+                        Some(file) => file.write(results.as_bytes()).await
+                            .map_err(|e| format!("Unable to write {}: {}", file.path().display(), e)),
+                        None => Ok(()),
+                    }
+                }, Message::WorksheetExported)
+            }
+            Message::WorksheetExported(result) => {
+                if let Err(e) = result {
+                    self.result = Some(Err(e));
+                    self.result_quantity = None;
+                    self.result_expr = None;
+                }
+                Task::none()
+            }
+            Message::CloseWorksheet => {
+                self.worksheet = None;
+                Task::none()
+            }
+            Message::ToggleAngles => {
+                self.show_angles = !self.show_angles;
+                Task::none()
+            }
+            Message::ToggleSlope => {
+                self.slope = match self.slope {
+                    Some(_) => None,
+                    None => Some(SlopeTool::default()),
+                };
+                Task::none()
+            }
+            Message::SlopeInput(field, input) => {
+                if let Some(slope) = self.slope.as_mut() {
+                    slope.set(field, input);
+                }
                 Task::none()
             }
             Message::ToggleMode => {
@@ -222,43 +615,61 @@ impl CalcWindow {
         }
     }
     pub(crate) fn view<'a>(&'a self) -> Element<Message> {
+        let validate = ui::preferences::manager().get::<bool>(ui::preferences::VALIDATE_INPUT).unwrap_or(false);
+        let plausible = !validate || self.calc.is_plausible_prefix(&self.content.text());
         let lcd = text_editor(&self.content)
             .height(Length::Fill)
-            .style(|theme: &Theme, status| {
+            .style(move |theme: &Theme, status| {
+                let palette = theme.extended_palette();
                 text_editor::Style {
                     background: Background::Color(Color::TRANSPARENT),
                     border: Border::default().width(Pixels::from(1))
-                        .color(theme.extended_palette().background.base.text),
+                        .color(if plausible { palette.background.base.text } else { palette.danger.base.color }),
                     .. text_editor::default(theme, status)
                 }
             })
             .on_action(|action| {
                 Message::EditorAction(action)
             })
+            .key_binding(editor_binding)
             .into();
 
-        let r64 = &self.result;
-        let result: Element<Message> = match r64 {
-                Some(r) => {
-                    match r {
-                        Ok(v) => {
-                            wrap_with_copy(text(Self::format_result(v)), v.clone())
-                        }
-                        Err(e) => text(e.clone()).into()
-                    }
-                }
-                None => text("".to_string()).into(),
-            };
-
+        let result: Element<Message> = match (self.preview, &self.result) {
+            // A provisional result is dimmed, as it hasn't been evaluated yet
+            (Some(preview), _) => text(display_result(&preview))
+                .style(|theme: &Theme| text::Style { color: Some(theme.extended_palette().background.strong.color) })
+                .into(),
+            (None, Some(Ok(v))) => {
+                let expr = self.result_expression().unwrap_or_else(|| self.content.text());
+                let description = describe_expression_and_result(&expr, *v);
+                wrap_with_copy(text(self.result_display(v)), v.clone(), Some(description))
+            }
+            (None, Some(Err(e))) => text(e.clone()).into(),
+            (None, None) => text("".to_string()).into(),
+        };
 
-        let mode: Element<Message> = text(self.calc.angle_mode().to_string())
-            .style(|theme: &Theme| {
-                text::Style {
-                    color: Some(theme.extended_palette().background.base.text),
+        // Clicking the mode cycles through the angle modes, like the DRG key
+        let mode: Element<Message> = Button::new(text(self.calc.angle_mode().to_string()))
+            .style(|theme: &Theme, _status| {
+                button::Style {
+                    background: Some(Background::Color(Color::TRANSPARENT)),
+                    text_color: theme.extended_palette().background.base.text,
+                    .. button::Style::default()
                 }
             })
+            .padding(Padding::from(0))
+            .on_press(Message::ToggleMode)
             .height(Length::Shrink)
             .into();
+        let mode = tooltip(mode, "Click or press F2 to change the angle mode, Esc clears", Position::Bottom)
+            .style(|theme| -> container::Style {
+                container::Style{
+                    text_color: Some(theme.extended_palette().primary.weak.text),
+                    background: Some(Background::from(theme.extended_palette().primary.weak.color)),
+                    border: Default::default(),
+                    shadow: Default::default(),
+                }
+            });
 
         let con_mode = Container::new(mode)
             .width(Length::Fill)
@@ -266,18 +677,25 @@ impl CalcWindow {
             .clip(false)
             .into();
 
+        let pref = ui::preferences::manager();
         let con_result = Container::new(result)
             .width(Length::Fill)
-            .align_x(Horizontal::Right)
+            .align_x(result_alignment(pref.get::<String>(ui::preferences::RESULT_ALIGN)))
             .clip(false)
             .into();
+        let con_result = Row::with_children([
+            icon_button(Bootstrap::Clipboard, Message::CopyExpression(self.content.text())),
+            icon_button(Bootstrap::ChevronLeft, Message::PreviousResult),
+            icon_button(Bootstrap::ChevronRight, Message::NextResult),
+            con_result,
+        ]).into();
 
-        let mb = build_menu_bar().into();
+        let mb = build_menu_bar(&self.history_filter, self.recording.is_some()).into();
 
         let menu_row = Row::with_children([mb, con_mode]).into();
 
         let sp = 2;
-        let top =
+        let mut top =
             if !self.is_converting {
                 Column::with_children([menu_row, lcd, con_result]).spacing(sp)
             } else {
@@ -302,8 +720,8 @@ impl CalcWindow {
                     Some(r) => {
                         match r {
                             Ok(v) => {
-                                let cv = try_convert(v, &self.convert_from, &self.convert_to);
-                                wrap_with_copy(text(Self::format_result(&cv)), cv)
+                                let cv = try_convert(v, &self.convert_from, &self.convert_to, input_figures());
+                                wrap_with_copy(text(display_converted(&cv, self.convert_to)), cv, None)
                             }
                             Err(e) => text(e.clone()).into()
                         }
@@ -316,8 +734,18 @@ impl CalcWindow {
                     .clip(false)
                     .into();
 
-                let r1 = Row::with_children([conv_from, con_result]).into();
-                let r2 = Row::with_children([conv_to, con_conv_result]).into();
+                let pin = match (self.convert_from, self.convert_to) {
+                    (Some(from), Some(to)) => icon_button(Bootstrap::Pin, Message::PinConversion(from, to)),
+                    _ => text("").into(),
+                };
+
+                let swap = icon_button(Bootstrap::ArrowDownUp, Message::SwapUnits);
+
+                let r1 = Row::with_children([conv_from, swap, con_result]).into();
+                let insert = icon_button(Bootstrap::BoxArrowInUp, Message::InsertConverted);
+                let share = icon_button(Bootstrap::Share, Message::CopyConversionSentence);
+
+                let r2 = Row::with_children([conv_to, pin, insert, share, con_conv_result]).into();
 
                 let rule1:Element<Message> = horizontal_rule(1)
                     .style(|theme| {
@@ -329,6 +757,24 @@ impl CalcWindow {
                     .into();
                 Column::with_children([menu_row, lcd, r1, rule1, r2]).spacing(sp)
             };
+        if let Some(pinned_bar) = self.pinned_bar() {
+            top = top.push(pinned_bar);
+        }
+        if let Some(words_bar) = self.words_bar() {
+            top = top.push(words_bar);
+        }
+        if let Some(angles_bar) = self.angles_bar() {
+            top = top.push(angles_bar);
+        }
+        if let Some(slope_bar) = self.slope_bar() {
+            top = top.push(slope_bar);
+        }
+        if let Some(worksheet_bar) = self.worksheet_bar() {
+            top = top.push(worksheet_bar);
+        }
+        if let Some(metadata_bar) = self.metadata_bar() {
+            top = top.push(metadata_bar);
+        }
         let lcd_container = container(top)
             .width(Length::Fill)
             .style(move |theme| {
@@ -350,7 +796,7 @@ impl CalcWindow {
         let b_seven = ButtonBuilder::new("7").make();
         let b_eight = ButtonBuilder::new("8").make();
         let b_nine = ButtonBuilder::new("9").make();
-        let b_zero = ButtonBuilder::new("0").make();
+        let b_zero = ButtonBuilder::new("0").span(2).make();
         let b_dec = ButtonBuilder::new(".").make();
         // Basic operations
         let b_plus = ButtonBuilder::new("+").make();
@@ -360,6 +806,8 @@ impl CalcWindow {
         let b_pow = ButtonBuilder::new("^").make();
         let b_lparen = ButtonBuilder::new("(").msg(Message::Func("".to_string())).make();
         let b_rparen = ButtonBuilder::new(")").make();
+        let b_recip = ButtonBuilder::new("1/x").msg(Message::Reciprocal).make();
+        let b_square = ButtonBuilder::new("x²").msg(Message::Square).make();
         // Functions
         let b_sin = ButtonBuilder::for_func("sin").make();
         let b_cos = ButtonBuilder::for_func("cos").make();
@@ -378,7 +826,9 @@ impl CalcWindow {
         let b_fact = ButtonBuilder::for_func("!").msg(Message::Func("factorial".to_string())).make();
         // Command buttons
         let b_equals = ButtonBuilder::new("=").msg(Message::Evaluate).span(2).make();
-        let b_clear = ButtonBuilder::new("AC")
+        let b_negate = ButtonBuilder::new("±").msg(Message::Negate).make();
+        let b_angles = ButtonBuilder::new("DRG→").msg(Message::ToggleAngles).make();
+        let b_clear = ButtonBuilder::new(if self.confirm_clear { "AC?" } else { "AC" })
             .msg(Message::Clear)
             .danger(true)
             .make();
@@ -386,21 +836,35 @@ impl CalcWindow {
         let b_right = ButtonBuilder::new("->").msg(Message::MoveRight).make();
         let b_back = ButtonBuilder::new("<del").msg(Message::BackSpace).make();
         let b_more = ButtonBuilder::new("DRG").msg(Message::ToggleMode).make();
+        // Memory
+        let b_mc = ButtonBuilder::new("MC").msg(Message::MemoryClear).make();
+        let b_mr = ButtonBuilder::new("MR").msg(Message::MemoryRecall).make();
+        let b_mplus = ButtonBuilder::new("M+").msg(Message::MemoryAdd).make();
+        let b_mminus = ButtonBuilder::new("M-").msg(Message::MemorySubtract).make();
 
         let row_height = Length::FillPortion(1);
+        let mut rows: Vec<Element<Message>> = vec![
+            Row::with_children([b_mc, b_mr, b_mplus, b_mminus]).spacing(sp).height(row_height).into(),
+        ];
+        if ui::preferences::manager().get::<bool>(ui::preferences::CONSTANTS_ROW).unwrap_or(false) {
+            let buttons = constant_buttons().into_iter().map(|b| b.make());
+            rows.push(Row::with_children(buttons).spacing(sp).height(row_height).into());
+        }
+        rows.extend([
+            Row::with_children([b_back, b_left, b_right, b_more, b_clear]).spacing(sp).height(row_height).into(),
+            Row::with_children([b_sin, b_cos, b_tan, b_sqrt, b_abs]).spacing(sp).height(row_height).into(),
+            Row::with_children([b_asin, b_acos, b_atan, b_ceil, b_floor]).spacing(sp).height(row_height).into(),
+            Row::with_children([b_exp, b_ln, b_log, b_log2, b_fact]).spacing(sp).height(row_height).into(),
+            Row::with_children([b_recip, b_square, b_pow, b_lparen, b_rparen]).spacing(sp).height(row_height).into(),
+            Row::with_children([b_seven, b_eight, b_nine, b_mult, b_div]).spacing(sp).height(row_height).into(),
+            Row::with_children([b_four, b_five, b_six, b_plus, b_minus]).spacing(sp).height(row_height).into(),
+            Row::with_children([b_one, b_two, b_three, b_equals]).spacing(sp).height(row_height).into(),
+            Row::with_children([b_zero, b_dec, b_negate, b_angles]).spacing(sp).height(row_height).into(),
+        ]);
         let col_all = Column::with_children([
             lcd_container.height(Length::FillPortion(3)).into(),
             Row::with_children([
-                Column::with_children([
-                    Row::with_children([b_back, b_left, b_right, b_more, b_clear]).spacing(sp).height(row_height).into(),
-                    Row::with_children([b_sin, b_cos, b_tan, b_sqrt, b_abs]).spacing(sp).height(row_height).into(),
-                    Row::with_children([b_asin, b_acos, b_atan, b_ceil, b_floor]).spacing(sp).height(row_height).into(),
-                    Row::with_children([b_exp, b_ln, b_log, b_log2, b_fact]).spacing(sp).height(row_height).into(),
-                    Row::with_children([b_seven, b_eight, b_nine, b_lparen, b_rparen]).spacing(sp).height(row_height).into(),
-                    Row::with_children([b_four, b_five, b_six, b_mult, b_div]).spacing(sp).height(row_height).into(),
-                    Row::with_children([b_one, b_two, b_three, b_plus, b_minus]).spacing(sp).height(row_height).into(),
-                    Row::with_children([b_zero, b_dec, b_equals, b_pow]).spacing(sp).height(row_height).into(),
-                ]).spacing(sp).into(),
+                Column::with_children(rows).spacing(sp).into(),
             ]).spacing(sp).height(Length::FillPortion(6)).into(),
         ]).spacing(sp);
 
@@ -416,17 +880,282 @@ impl CalcWindow {
             .padding(5).into()
     }
 
-    fn format_result(v: &f64) -> String {
-        if v.abs() < 0.001 || v.abs() > 10000000.0 {
-            format!("= {:+e}", v)
+    /// Rewrite the selection, or the whole expression if nothing is selected, e.g. to take
+    /// its reciprocal. Like a function, inserting over a selection replaces it.
+    fn wrap_expression(&mut self, wrap: fn(&str) -> String) {
+        let target = match self.content.selection() {
+            Some(sel) => sel,
+            None => {
+                let text = self.content.text().trim().to_string();
+                if text.is_empty() {
+                    return;
+                }
+                self.content.perform(Action::Move(Motion::DocumentStart));
+                self.content.perform(Action::Select(Motion::DocumentEnd));
+                text
+            }
+        };
+        for c in wrap(&target).chars() {
+            self.content.perform(Action::Edit(Edit::Insert(c)));
+        }
+    }
+
+    /// Store a value in memory, keeping it for the next session.
+    fn set_memory(&mut self, value: f64) {
+        self.memory = value;
+        ui::preferences::manager().put(ui::preferences::MEMORY, value);
+    }
+
+    /// Preview the result of the expression while it is being typed. Until it can be evaluated
+    /// nothing is shown, and once it has been evaluated, or while stepping through earlier
+    /// results, the real result is shown instead.
+    fn update_preview(&mut self) {
+        let expr = self.content.text();
+        let expr = expr.trim();
+        self.preview = match &self.result_expr {
+            _ if self.result_index.is_some() => None,
+            Some(committed) if committed == expr => None,
+            Some(_) if self.replacement_expression().as_deref() == Some(expr) => None,
+            _ => self.calc.preview(expr),
+        };
+    }
+
+    /// If auto evaluation is turned on, show the result as soon as the expression's parentheses
+    /// are balanced and it is otherwise complete. Only the result is updated, the expression is
+    /// recorded in history and replaced by its result when `=` is pressed.
+    fn auto_evaluate(&mut self) {
+        let pref = ui::preferences::manager();
+        if pref.get::<bool>(ui::preferences::AUTO_EVALUATE).unwrap_or(false) {
+            let expr = self.content.text().trim().to_string();
+            if self.calc.is_complete(&expr) {
+                let result = self.calc.evaluate_preview(&expr);
+                self.result_expr = Some(expr);
+                self.result_quantity = result.as_ref().ok().cloned();
+                self.result = Some(result.map(|q| q.value));
+                self.result_index = None;
+            }
+        }
+    }
+
+    /// Evaluate the expression in the editor, keeping the unit of the result if it has one.
+    fn evaluate_expression(&mut self) {
+        // Close any parentheses left open, unless the user would rather be told
+        let strict = ui::preferences::manager().get::<bool>(ui::preferences::STRICT_PARENS).unwrap_or(false);
+        if !strict {
+            self.content.perform(Action::Move(Motion::DocumentEnd));
+            for _ in 0..missing_close_parens(&self.content.text()) {
+                self.content.perform(Action::Edit(Edit::Insert(')')));
+            }
+        }
+        let expr = self.content.text().trim().to_string();
+        let result = self.calc.evaluate(&expr);
+        self.result_expr = Some(expr);
+        self.result_quantity = result.as_ref().ok().cloned();
+        self.result = Some(result.map(|q| q.value));
+        self.result_index = None;
+        self.record_conversion();
+        // History keeps the original expression, the editor carries on from the result
+        if ui::preferences::manager().get::<bool>(ui::preferences::RESULT_AS_EXPRESSION).unwrap_or(false) {
+            if let Some(text) = self.replacement_expression() {
+                self.content.perform(Action::Move(Motion::DocumentStart));
+                self.content.perform(Action::Select(Motion::DocumentEnd));
+                for c in text.chars() {
+                    self.content.perform(Action::Edit(Edit::Insert(c)));
+                }
+            }
+        }
+    }
+
+    /// The result written as an expression, to continue calculating from.
+    fn replacement_expression(&self) -> Option<String> {
+        let quantities = ui::preferences::manager().get::<bool>(ui::preferences::QUANTITIES).unwrap_or(false);
+        self.result_quantity.as_ref().and_then(|q| result_expression(q, quantities))
+    }
+
+    /// Remember the conversion of the current result, so it can be repeated from the menu.
+    fn record_conversion(&self) {
+        if let (true, Some(Ok(v)), Some(from), Some(to)) = (self.is_converting, &self.result, self.convert_from, self.convert_to) {
+            // Record the conversion as it is shown, from the rounded input if there is one
+            let figures = input_figures();
+            recent::manager().add(Conversion {
+                dimension: from.dimension().to_string(),
+                from: from.name.to_string(),
+                to: to.name.to_string(),
+                input: figures.map_or(*v, |figures| round_significant(*v, figures)),
+                output: try_convert(v, &self.convert_from, &self.convert_to, figures),
+            });
+            recent::manager().save();
+        }
+    }
+
+    /// Show an earlier or later result from history in the result area, leaving the editor alone.
+    fn step_result(&mut self, back: bool) {
+        let entries = history::manager().history().entries().read()
+            .map(|entries| entries.iter().cloned().collect::<Vec<_>>())
+            .unwrap_or_default();
+        self.result_index = step_result_index(self.result_index, back, entries.len());
+        if let Some(entry) = self.result_index.and_then(|i| entries.get(i)) {
+            self.result = Some(Ok(entry.value));
+            self.result_quantity = None;
+        }
+    }
+
+    /// Format the result with its units, or as an angle if showing degrees, minutes and seconds.
+    fn result_display(&self, v: &f64) -> String {
+        let (unit, exact, compound) = self.result_quantity.as_ref()
+            .map_or((None, None, None), |q| {
+                (q.unit, q.exact.map(|e| (e, q.approximate)), q.compound_display())
+            });
+        let pref = ui::preferences::manager();
+        let dms = pref.get::<bool>(ui::preferences::DMS_OUTPUT).unwrap_or(false);
+        let durations = pref.get::<bool>(ui::preferences::DURATIONS).unwrap_or(false);
+        let pi_multiple = if pref.get::<bool>(ui::preferences::PI_MULTIPLES).unwrap_or(false) {
+            as_pi_multiple(*v)
         } else {
-            let formatted = format!("= {0:.1$}", v, 10);
-            formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+            None
+        };
+        let formatted = match (compound, unit, exact) {
+            (Some(compound), _, _) => format!("= {}", compound),
+            (None, Some(unit), _) => format!("{} {}", display_result(v), unit.symbol),
+            (None, None, _) if durations => format!("= {}", format_duration(*v)),
+            // Decimal results that fell back to floating point are marked as approximate
+            (None, None, Some((exact, true))) => format!("≈ {}", exact.normalize()),
+            (None, None, Some((exact, false))) => format!("= {}", exact.normalize()),
+            (None, None, None) => match pi_multiple {
+                Some(pi_multiple) => format!("= {}", pi_multiple),
+                None if dms => format!("= {}", format_dms(*v)),
+                None => display_result(v),
+            },
+        };
+        let show_expr = pref.get::<bool>(ui::preferences::SHOW_EXPRESSION).unwrap_or(false);
+        match self.result_expression() {
+            Some(expr) => result_line(&expr, &formatted),
+            None => match &self.result_expr {
+                Some(expr) if show_expr => result_line(expr, &formatted),
+                _ => formatted,
+            },
         }
     }
 
+    /// The expression for the history result being shown, if stepping through results.
+    fn result_expression(&self) -> Option<String> {
+        let index = self.result_index?;
+        history::manager().history().entries().read().ok()?
+            .get(index)
+            .map(|entry| entry.expr.clone())
+    }
+
+    /// Build the bar spelling out the current result in words, if that option is on.
+    fn words_bar(&self) -> Option<Element<Message>> {
+        if !ui::preferences::manager().get::<bool>(ui::preferences::NUMBER_WORDS).unwrap_or(false) {
+            return None;
+        }
+        let Some(Ok(v)) = &self.result else {
+            return None;
+        };
+        let words = text(number_to_words(*v)?).size(12);
+        Some(Container::new(words)
+            .width(Length::Fill)
+            .align_x(Horizontal::Right)
+            .clip(false)
+            .into())
+    }
+
+    /// Build the bar showing the result, taken as an angle, in the units of the other angle modes.
+    fn angles_bar(&self) -> Option<Element<Message>> {
+        let Some(Ok(v)) = &self.result else {
+            return None;
+        };
+        if !self.show_angles || !v.is_finite() {
+            return None;
+        }
+        let angles = in_other_modes(*v, self.calc.angle_mode()).iter()
+            .map(|(unit, angle)| format!("{} {}", display_result(angle), unit.symbol))
+            .collect::<Vec<_>>()
+            .join("   ");
+        Some(Container::new(text(angles).size(12))
+            .width(Length::Fill)
+            .align_x(Horizontal::Right)
+            .clip(false)
+            .into())
+    }
+
+    /// Build the bar for the slope tool, with its angle, grade and ratio fields, if it is open.
+    fn slope_bar(&self) -> Option<Element<Message>> {
+        let slope = self.slope.as_ref()?;
+        let input = |value: &str, field: SlopeField| -> Element<Message> {
+            text_input("", value)
+                .on_input(move |s| Message::SlopeInput(field, s))
+                .padding(Padding::from([0, 2]))
+                .width(Length::Fill)
+                .into()
+        };
+        let mut row = Row::with_children([
+            icon_button(Bootstrap::X, Message::ToggleSlope),
+            input(&slope.angle, SlopeField::Angle),
+            text("°  ").into(),
+            input(&slope.grade, SlopeField::Grade),
+            text("%  1:").into(),
+            input(&slope.ratio, SlopeField::Ratio),
+        ]);
+        if let Some(error) = &slope.error {
+            row = row.push(text(format!("  {}", error)).size(12));
+        }
+        Some(row.into())
+    }
+
+    /// Build the bar showing the results of a worksheet that has been run, if there is one.
+    fn worksheet_bar(&self) -> Option<Element<Message>> {
+        let lines = self.worksheet.as_ref()?;
+        let results = worksheet_text(lines, &FormatOptions::from_preferences());
+        let buttons = Column::with_children([
+            icon_button(Bootstrap::X, Message::CloseWorksheet),
+            icon_button(Bootstrap::Download, Message::ExportWorksheet),
+        ]).into();
+        let results = Container::new(scrollable(text(results.trim_end().to_string()).size(12)).width(Length::Fill))
+            .max_height(100.0)
+            .into();
+        Some(Row::with_children([buttons, results]).into())
+    }
+
+    /// Build the bar showing the angle mode and time of a result recalled from history.
+    fn metadata_bar(&self) -> Option<Element<Message>> {
+        let index = self.result_index?;
+        let metadata = history::manager().history().entries().read().ok()?
+            .get(index)
+            .map(history_metadata)
+            .filter(|metadata| !metadata.is_empty())?;
+        Some(Container::new(text(metadata).size(12))
+            .width(Length::Fill)
+            .align_x(Horizontal::Right)
+            .clip(false)
+            .into())
+    }
+
+    /// Build the bar showing the pinned conversion of the current result, if there is one.
+    fn pinned_bar(&self) -> Option<Element<Message>> {
+        let (from, to) = self.pinned?;
+        let cv = pinned_value(&self.result, &self.pinned)?;
+
+        let unpin = icon_button(Bootstrap::PinFill, Message::UnpinConversion);
+        let label = text(format!("{} -> {}", from.name, to.name))
+            .align_x(Horizontal::Left)
+            .into();
+        let value = Container::new(wrap_with_copy(text(display_converted(&cv, Some(to))), cv, None))
+            .width(Length::Fill)
+            .align_x(Horizontal::Right)
+            .clip(false)
+            .into();
+        Some(Row::with_children([unpin, label, value]).into())
+    }
+
     pub(crate) fn subscription(&self) -> Subscription<Message> {
-        event::listen_with(|event, _status, _id| {
+        let shortcuts = if self.is_converting {
+            event::listen_with(|event, status, _id| key_event(event, status, true))
+        } else {
+            event::listen_with(|event, status, _id| key_event(event, status, false))
+        };
+        let events = event::listen_with(|event, _status, _id| {
             match event {
                 Event::Window(window::Event::Resized(size)) => {
                     Some(Message::WindowResized(size.width, size.height))
@@ -439,7 +1168,26 @@ impl CalcWindow {
                 }
                 _ => None
             }
-        })
+        });
+        match autosave_interval() {
+            Some(interval) => {
+                let autosave = time::every(interval).map(|_| Message::AutoSave);
+                Subscription::batch([events, shortcuts, autosave])
+            }
+            None => Subscription::batch([events, shortcuts]),
+        }
+    }
+
+    /// A snapshot of the work in progress. Results that can't be written as JSON aren't kept.
+    fn session(&self) -> Session {
+        Session {
+            expression: self.content.text().trim_end_matches('\n').to_string(),
+            result: self.result.clone().filter(|r| r.as_ref().map_or(true, |v| v.is_finite())),
+            angle_mode: self.calc.angle_mode().to_string(),
+            converting: self.is_converting,
+            convert_from: self.convert_from.map(|u| u.name.to_string()),
+            convert_to: self.convert_to.map(|u| u.name.to_string()),
+        }
     }
 
     pub(crate) fn theme(&self) -> Theme {
@@ -447,7 +1195,9 @@ impl CalcWindow {
     }
 }
 
-fn wrap_with_copy(text: Text, value: f64) -> Element<Message> {
+/// Make a value clickable to copy it. The tooltip can carry a description of the value in words,
+/// as iced does not yet expose an accessibility tree to attach it to.
+fn wrap_with_copy(text: Text, value: f64, description: Option<String>) -> Element<Message> {
     let b= Button::new(text)
         .style(|theme: &Theme, _status| {
             button::Style {
@@ -460,7 +1210,11 @@ fn wrap_with_copy(text: Text, value: f64) -> Element<Message> {
         .on_press(Message::Copy(value))
         .height(Length::Shrink);
 
-    tooltip(b, "Click to copy", Position::Left)
+    let tip = match description {
+        Some(description) => format!("{}\nClick to copy", description),
+        None => String::from("Click to copy"),
+    };
+    tooltip(b, Text::new(tip), Position::Left)
         .style(|theme| -> container::Style {
             container::Style{
                 text_color: Some(theme.extended_palette().primary.weak.text),
@@ -472,6 +1226,279 @@ fn wrap_with_copy(text: Text, value: f64) -> Element<Message> {
         .into()
 }
 
+/// How often the session is saved, or None if it isn't.
+fn autosave_interval() -> Option<Duration> {
+    let seconds = ui::preferences::manager().get::<u64>(ui::preferences::AUTOSAVE_INTERVAL)
+        .unwrap_or(session::DEFAULT_INTERVAL);
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+/// Set up the calculator from the options in preferences.
+fn apply_options(calc: &mut Calc) {
+    let pref = ui::preferences::manager();
+    calc.set_quantities(pref.get::<bool>(ui::preferences::QUANTITIES).unwrap_or(false));
+    calc.set_decimals(pref.get::<bool>(ui::preferences::DECIMAL_MODE).unwrap_or(false));
+    calc.set_integers(pref.get::<bool>(ui::preferences::INTEGER_MODE).unwrap_or(false));
+    calc.set_durations(pref.get::<bool>(ui::preferences::DURATIONS).unwrap_or(false));
+    calc.set_decimal_comma(pref.get::<String>(ui::preferences::DECIMAL_SEPARATOR).is_some_and(|s| s == ","));
+    set_dpi(pref.get::<f64>(ui::preferences::DPI).unwrap_or(DEFAULT_DPI));
+}
+
+/// The number of closing parentheses needed to balance an expression. Stray closing
+/// parentheses don't cancel out ones opened after them.
+fn missing_close_parens(expr: &str) -> usize {
+    expr.chars().fold(0, |open: usize, c| match c {
+        '(' => open + 1,
+        ')' => open.saturating_sub(1),
+        _ => open,
+    })
+}
+
+/// The reciprocal of an expression, e.g. `1/(2+3)`.
+fn reciprocal(expr: &str) -> String {
+    format!("1/({})", expr.trim())
+}
+
+/// The square of an expression. Anything more than a single number or name is put in
+/// parentheses so that the whole of it is squared, e.g. `(2+3)^2`.
+fn square(expr: &str) -> String {
+    let expr = expr.trim();
+    if is_simple_term(expr) {
+        format!("{}^2", expr)
+    } else {
+        format!("({})^2", expr)
+    }
+}
+
+/// Whether an expression is a single number or name, e.g. `2.5` or `π`.
+fn is_simple_term(expr: &str) -> bool {
+    !expr.is_empty() && expr.chars().all(|c| c.is_alphanumeric() || c == '.')
+}
+
+/// Whether the whole of an expression is in one pair of parentheses, e.g. `(1+2)` but not `(1)+(2)`.
+fn is_parenthesised(expr: &str) -> bool {
+    if !expr.starts_with('(') || !expr.ends_with(')') {
+        return false;
+    }
+    let mut depth = 0;
+    for (i, c) in expr.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            _ => {}
+        }
+        if depth == 0 {
+            return i == expr.len() - 1;
+        }
+    }
+    false
+}
+
+/// Change the sign of an expression, removing a leading negation if it already has one,
+/// e.g. `5` becomes `-5`, `1+2` becomes `-(1+2)` and `-(1+2)` becomes `1+2`.
+fn negate(expr: &str) -> String {
+    let expr = expr.trim();
+    match expr.strip_prefix('-') {
+        Some(rest) if is_simple_term(rest) => rest.to_string(),
+        Some(rest) if is_parenthesised(rest) => rest[1..rest.len() - 1].to_string(),
+        _ if is_simple_term(expr) || is_parenthesised(expr) => format!("-{}", expr),
+        _ => format!("-({})", expr),
+    }
+}
+
+/// Map the result alignment preference to an alignment. Results are right aligned by default.
+fn result_alignment(name: Option<String>) -> Horizontal {
+    match name.as_deref() {
+        Some("left") => Horizontal::Left,
+        Some("center") => Horizontal::Center,
+        _ => Horizontal::Right,
+    }
+}
+
+/// The number of significant figures conversion inputs are rounded to, if they are rounded.
+fn input_figures() -> Option<usize> {
+    ui::preferences::manager().get::<usize>(ui::preferences::INPUT_FIGURES)
+}
+
+/// The pinned conversion is shown whenever there is a numeric result to convert.
+fn pinned_value(result: &Option<Result<f64, String>>, pinned: &Option<(&'static Unit, &'static Unit)>) -> Option<f64> {
+    match (result, pinned) {
+        (Some(Ok(v)), Some((from, to))) if v.is_finite() => Some(convert(v, from, to)),
+        _ => None,
+    }
+}
+
+/// The units to convert between when starting in conversion mode: the last conversion made,
+/// or kilometres to miles if there isn't one.
+fn start_conversion(start_in_convert: bool, last: Option<(&str, &str)>) -> Option<(&'static Unit, &'static Unit)> {
+    if !start_in_convert {
+        return None;
+    }
+    last.and_then(|(from, to)| find_unit(from).zip(find_unit(to)))
+        .or(Some((&KILOMETRE, &MILE)))
+}
+
+/// Expressions at least this long need AC pressed twice to clear, if that option is on.
+static CLEAR_CONFIRM_LENGTH: usize = 30;
+
+/// Decide whether AC clears the expression now. A long expression is only cleared if AC was
+/// pressed immediately before, so one stray press doesn't lose it.
+fn clear_confirmed(length: usize, confirming: bool, confirm_option: bool) -> bool {
+    !confirm_option || confirming || length < CLEAR_CONFIRM_LENGTH
+}
+
+/// Add the current result to, or subtract it from, the memory. Without a result the memory is unchanged.
+fn memory_update(memory: f64, result: &Option<Result<f64, String>>, subtract: bool) -> f64 {
+    match result {
+        Some(Ok(v)) if v.is_finite() => if subtract { memory - v } else { memory + v },
+        _ => memory,
+    }
+}
+
+/// Move the index of the result shown from history. Index 0 is the most recent entry, so stepping
+/// back moves to older entries. With no index the most recent result is taken to be showing.
+fn step_result_index(index: Option<usize>, back: bool, len: usize) -> Option<usize> {
+    if len == 0 {
+        return None;
+    }
+    let current = index.unwrap_or(0).min(len - 1);
+    Some(if back { (current + 1).min(len - 1) } else { current.saturating_sub(1) })
+}
+
+/// Route key presses to the keyboard shortcuts.
+fn key_event(event: Event, status: event::Status, is_converting: bool) -> Option<Message> {
+    match event {
+        Event::Keyboard(keyboard::Event::KeyPressed { key, modifiers, .. }) if is_shortcut(&key, status) => {
+            key_shortcut(key, modifiers, is_converting)
+        }
+        _ => None
+    }
+}
+
+/// Keys a widget has used, e.g. for text entry, are left alone, except F2 which is never part of
+/// an expression. Escape is only a shortcut when nothing used it, so it can still close a menu or
+/// leave the history search. The editor handles Escape itself, see `editor_binding`.
+fn is_shortcut(key: &Key, status: event::Status) -> bool {
+    status == event::Status::Ignored || matches!(key, Key::Named(Named::F2))
+}
+
+/// Escape clears the expression while the editor has focus, rather than taking the focus away.
+/// Other keys keep their usual bindings.
+fn editor_binding(key_press: KeyPress) -> Option<Binding<Message>> {
+    match key_press.key.as_ref() {
+        Key::Named(Named::Escape) => Some(Binding::Custom(Message::Clear)),
+        _ => Binding::from_key_press(key_press),
+    }
+}
+
+/// Map a key press to a message.
+/// Escape clears the expression and F2 cycles the angle mode.
+/// Ctrl+S (Cmd+S on macOS) swaps the conversion direction, but only while converting.
+/// Ctrl+V pastes into the expression when the editor does not have focus.
+/// Tab and Shift+Tab move the focus between the text inputs, i.e. the expression and the history
+/// search, and nothing else. Buttons, pickers and the menu bar are not reachable with Tab, as iced
+/// widgets other than text inputs can't take focus. The keys the keypad buttons stand for can be
+/// typed into the expression instead.
+fn key_shortcut(key: Key, modifiers: Modifiers, is_converting: bool) -> Option<Message> {
+    match key.as_ref() {
+        Key::Named(Named::Tab) if modifiers.shift() => Some(Message::FocusPrevious),
+        Key::Named(Named::Tab) => Some(Message::FocusNext),
+        Key::Named(Named::Escape) => Some(Message::Clear),
+        Key::Named(Named::F2) => Some(Message::ToggleMode),
+        Key::Character("s") if modifiers.command() && is_converting => Some(Message::SwapUnits),
+        Key::Character("v") if modifiers.command() => Some(Message::Paste),
+        _ => None,
+    }
+}
+
+/// The buttons of the optional row of common constants.
+fn constant_buttons() -> Vec<ButtonBuilder<'static>> {
+    [&Pi, &Euler, &C, &G].into_iter()
+        .map(ButtonBuilder::for_constant)
+        .collect()
+}
+
+/// Expressions are a single line, so line breaks in pasted text are dropped.
+fn single_line(text: &str) -> String {
+    text.chars().filter(|c| *c != '\n' && *c != '\r').collect()
+}
+
+/// Split a definition such as `myrate = 0.07` into the constant name and the expression for its value.
+fn split_definition(text: &str) -> Option<(&str, &str)> {
+    let (name, expr) = text.split_once('=')?;
+    let (name, expr) = (name.trim(), expr.trim());
+    if name.is_empty() || expr.is_empty() {
+        None
+    } else {
+        Some((name, expr))
+    }
+}
+
+/// The linked fields of the slope tool, as they are shown. Editing one fills in the others.
+#[derive(Debug, Default)]
+struct SlopeTool {
+    angle: String,
+    grade: String,
+    ratio: String,
+    error: Option<String>,
+}
+
+impl SlopeTool {
+    /// Set a field to what was typed. Once it is a valid slope the other fields show the same slope,
+    /// otherwise they are left as they are. A ratio can be typed with or without its `1:`.
+    fn set(&mut self, field: SlopeField, input: String) {
+        let value = input.trim().trim_start_matches("1:").parse::<f64>();
+        *self.field_mut(field) = input;
+        let slope = match value {
+            Ok(value) => Slope::from_field(field, value),
+            Err(_) => return,
+        };
+        match slope {
+            Ok(slope) => {
+                for other in [SlopeField::Angle, SlopeField::Grade, SlopeField::Ratio] {
+                    if other != field {
+                        *self.field_mut(other) = slope_text(slope.value(other));
+                    }
+                }
+                self.error = None;
+            }
+            Err(e) => self.error = Some(e),
+        }
+    }
+
+    fn field_mut(&mut self, field: SlopeField) -> &mut String {
+        match field {
+            SlopeField::Angle => &mut self.angle,
+            SlopeField::Grade => &mut self.grade,
+            SlopeField::Ratio => &mut self.ratio,
+        }
+    }
+}
+
+/// Slopes are shown to 4 decimal places, enough for any building work, so they can be edited.
+fn slope_text(v: f64) -> String {
+    if v.is_infinite() {
+        return String::from("∞");
+    }
+    let formatted = format!("{:.4}", v);
+    formatted.trim_end_matches('0').trim_end_matches('.').to_string()
+}
+
+fn icon_button<'a>(icon: Bootstrap, msg: Message) -> Element<'a, Message> {
+    Button::new(text(icon_to_string(icon)).font(BOOTSTRAP_FONT))
+        .style(|theme: &Theme, _status| {
+            button::Style {
+                background: Some(Background::Color(Color::TRANSPARENT)),
+                text_color: theme.extended_palette().background.base.text,
+                .. button::Style::default()
+            }
+        })
+        .padding(Padding::from([0, 3]))
+        .on_press(msg)
+        .height(Length::Shrink)
+        .into()
+}
+
 /// A builder for making the button widgets.
 /// Note that the functions all take ownership of self and then return self; this allows
 /// us to avoid returning mutable references and so avoid ugly 'static life times.
@@ -533,6 +1560,24 @@ impl <'a> ButtonBuilder<'a> {
         Self {name, msg: Some(Message::Func(name.to_string())), danger: false, span: 1}
     }
 
+    /// Get a new builder for a button that inserts a constant.
+    /// This will add a message of the form ```Message::Constant(name.to_string()))```
+    ///
+    /// # Arguments
+    ///
+    /// * `constant`: The constant, whose name is shown on the button
+    ///
+    /// returns: ButtonBuilder
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// ButtonBuilder::for_constant(&Pi)
+    /// ```
+    fn for_constant(constant: &'static Constant) -> Self {
+        Self {name: constant.name(), msg: Some(Message::Constant(constant.name().to_string())), danger: false, span: 1}
+    }
+
     /// Add the message to be generated by the button. This will replace any default message.
     ///
     /// # Arguments
@@ -720,3 +1765,214 @@ fn theme_by_name(name: Option<String>) -> &'static Theme {
     }
     &ui::lcd_theme()
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::assert_near;
+    use crate::conversions::length::{KILOMETRE, MILE};
+    use iced::alignment::Horizontal;
+
+    use iced::event;
+    use iced::keyboard::{Key, Modifiers};
+    use iced::keyboard::key::Named;
+    use iced::widget::text_editor::{self, Binding, KeyPress};
+
+    use crate::ui::calc_window::{clear_confirmed, constant_buttons, editor_binding, is_shortcut, key_shortcut, memory_update, missing_close_parens, negate, pinned_value, reciprocal, result_alignment, single_line, SlopeTool, split_definition, square, start_conversion, step_result_index};
+    use crate::conversions::slope::SlopeField;
+    use crate::ui::messages::Message;
+
+    #[test]
+    fn test_pinned_value() {
+        let pinned = Some((&KILOMETRE, &MILE));
+        assert_near!(pinned_value(&Some(Ok(10.0)), &pinned).unwrap(), 6.2137102);
+        assert_eq!(pinned_value(&Some(Ok(10.0)), &None), None);
+        assert_eq!(pinned_value(&None, &pinned), None);
+        assert_eq!(pinned_value(&Some(Err("Oops".to_string())), &pinned), None);
+        assert_eq!(pinned_value(&Some(Ok(f64::NAN)), &pinned), None);
+    }
+
+    #[test]
+    fn test_start_conversion() {
+        assert!(start_conversion(false, Some(("Metre", "Foot"))).is_none());
+        let (from, to) = start_conversion(true, Some(("Metre", "Foot"))).unwrap();
+        assert_eq!((from.name, to.name), ("Metre", "Foot"));
+        let (from, to) = start_conversion(true, None).unwrap();
+        assert_eq!((from.name, to.name), (KILOMETRE.name, MILE.name));
+        let (from, _) = start_conversion(true, Some(("Cubit", "Foot"))).unwrap();
+        assert_eq!(from.name, KILOMETRE.name);
+    }
+
+    #[test]
+    fn test_result_alignment() {
+        assert_eq!(result_alignment(Some("left".to_string())), Horizontal::Left);
+        assert_eq!(result_alignment(Some("center".to_string())), Horizontal::Center);
+        assert_eq!(result_alignment(Some("right".to_string())), Horizontal::Right);
+        assert_eq!(result_alignment(Some("sideways".to_string())), Horizontal::Right);
+        assert_eq!(result_alignment(None), Horizontal::Right);
+    }
+
+    #[test]
+    fn test_swap_shortcut() {
+        let s = Key::Character("s".into());
+        assert!(matches!(key_shortcut(s.clone(), Modifiers::COMMAND, true), Some(Message::SwapUnits)));
+        assert!(key_shortcut(s.clone(), Modifiers::COMMAND, false).is_none());
+        assert!(key_shortcut(s, Modifiers::empty(), true).is_none());
+        assert!(key_shortcut(Key::Character("x".into()), Modifiers::COMMAND, true).is_none());
+    }
+
+    #[test]
+    fn test_paste_shortcut() {
+        let v = Key::Character("v".into());
+        assert!(matches!(key_shortcut(v.clone(), Modifiers::COMMAND, false), Some(Message::Paste)));
+        assert!(key_shortcut(v, Modifiers::empty(), false).is_none());
+    }
+
+    #[test]
+    fn test_named_shortcuts() {
+        assert!(matches!(key_shortcut(Key::Named(Named::Escape), Modifiers::empty(), false), Some(Message::Clear)));
+        assert!(matches!(key_shortcut(Key::Named(Named::F2), Modifiers::empty(), true), Some(Message::ToggleMode)));
+        assert!(key_shortcut(Key::Named(Named::F3), Modifiers::empty(), false).is_none());
+    }
+
+    #[test]
+    fn test_escape_only_when_unused() {
+        let escape = Key::Named(Named::Escape);
+        assert!(is_shortcut(&escape, event::Status::Ignored));
+        assert!(!is_shortcut(&escape, event::Status::Captured));
+        assert!(is_shortcut(&Key::Named(Named::F2), event::Status::Captured));
+        assert!(!is_shortcut(&Key::Character("s".into()), event::Status::Captured));
+    }
+
+    #[test]
+    fn test_editor_escape_clears() {
+        let press = |key: Key| KeyPress {
+            key,
+            modifiers: Modifiers::empty(),
+            text: None,
+            status: text_editor::Status::Focused,
+        };
+        assert!(matches!(editor_binding(press(Key::Named(Named::Escape))), Some(Binding::Custom(Message::Clear))));
+        assert!(matches!(editor_binding(press(Key::Named(Named::Backspace))), Some(Binding::Backspace)));
+    }
+
+    #[test]
+    fn test_focus_shortcuts() {
+        let tab = Key::Named(Named::Tab);
+        assert!(matches!(key_shortcut(tab.clone(), Modifiers::empty(), false), Some(Message::FocusNext)));
+        assert!(matches!(key_shortcut(tab.clone(), Modifiers::SHIFT, false), Some(Message::FocusPrevious)));
+        assert!(matches!(key_shortcut(tab, Modifiers::empty(), true), Some(Message::FocusNext)));
+    }
+
+    #[test]
+    fn test_constant_buttons() {
+        let buttons = constant_buttons();
+        let names: Vec<&str> = buttons.iter().map(|b| b.name).collect();
+        assert_eq!(names, vec!["π", "ℇ", "C", "G"]);
+        for b in buttons {
+            assert!(matches!(b.msg, Some(Message::Constant(ref name)) if name == b.name));
+        }
+    }
+
+    #[test]
+    fn test_single_line() {
+        assert_eq!(single_line("1 +\r\n2\n"), "1 +2");
+        assert_eq!(single_line("sqrt(2)"), "sqrt(2)");
+    }
+
+    #[test]
+    fn test_reciprocal_square() {
+        assert_eq!(reciprocal("2+3"), "1/(2+3)");
+        assert_eq!(reciprocal(" 4 "), "1/(4)");
+        assert_eq!(square("4"), "4^2");
+        assert_eq!(square("2.5"), "2.5^2");
+        assert_eq!(square("π"), "π^2");
+        assert_eq!(square("2+3"), "(2+3)^2");
+        assert_eq!(square(&reciprocal("x")), "(1/(x))^2");
+    }
+
+    #[test]
+    fn test_missing_close_parens() {
+        assert_eq!(missing_close_parens("1 + 2"), 0);
+        assert_eq!(missing_close_parens("sin(30"), 1);
+        assert_eq!(missing_close_parens("sqrt(max(2, 3"), 2);
+        assert_eq!(missing_close_parens("(1 + 2) * (3"), 1);
+        assert_eq!(missing_close_parens("(1 + 2))"), 0);
+        assert_eq!(missing_close_parens(")("), 1);
+    }
+
+    #[test]
+    fn test_negate() {
+        assert_eq!(negate("5"), "-5");
+        assert_eq!(negate("-5"), "5");
+        assert_eq!(negate("2.5e3"), "-2.5e3");
+        assert_eq!(negate("π"), "-π");
+        assert_eq!(negate("1+2"), "-(1+2)");
+        assert_eq!(negate("-(1+2)"), "1+2");
+        assert_eq!(negate("(1+2)"), "-(1+2)");
+        assert_eq!(negate("(1)+(2)"), "-((1)+(2))");
+        assert_eq!(negate("-(1)+(2)"), "-(-(1)+(2))");
+        assert_eq!(negate("-3 + 4"), "-(-3 + 4)");
+        assert_eq!(negate(&negate("sin(30) * 2")), "sin(30) * 2");
+    }
+
+    #[test]
+    fn test_slope_tool() {
+        let mut slope = SlopeTool::default();
+        slope.set(SlopeField::Angle, "45".to_string());
+        assert_eq!((slope.grade.as_str(), slope.ratio.as_str()), ("100", "1"));
+
+        slope.set(SlopeField::Ratio, "1:12".to_string());
+        assert_eq!((slope.angle.as_str(), slope.grade.as_str()), ("4.7636", "8.3333"));
+        assert_eq!(slope.ratio, "1:12");
+
+        // Nothing changes until there is a number
+        slope.set(SlopeField::Grade, "".to_string());
+        assert_eq!((slope.angle.as_str(), slope.ratio.as_str()), ("4.7636", "1:12"));
+        slope.set(SlopeField::Grade, "0".to_string());
+        assert_eq!((slope.angle.as_str(), slope.ratio.as_str()), ("0", "∞"));
+
+        slope.set(SlopeField::Angle, "90".to_string());
+        assert!(slope.error.is_some());
+        assert_eq!(slope.grade, "0");
+    }
+
+    #[test]
+    fn test_split_definition() {
+        assert_eq!(split_definition("myrate = 0.07"), Some(("myrate", "0.07")));
+        assert_eq!(split_definition("g0=9.8 * 1"), Some(("g0", "9.8 * 1")));
+        assert_eq!(split_definition("= 3"), None);
+        assert_eq!(split_definition("x ="), None);
+        assert_eq!(split_definition("1 + 2"), None);
+    }
+
+    #[test]
+    fn test_step_result_index() {
+        assert_eq!(step_result_index(None, true, 0), None);
+        assert_eq!(step_result_index(None, true, 3), Some(1));
+        assert_eq!(step_result_index(Some(1), true, 3), Some(2));
+        assert_eq!(step_result_index(Some(2), true, 3), Some(2));
+        assert_eq!(step_result_index(Some(2), false, 3), Some(1));
+        assert_eq!(step_result_index(Some(0), false, 3), Some(0));
+        assert_eq!(step_result_index(None, false, 3), Some(0));
+        assert_eq!(step_result_index(Some(5), false, 3), Some(1));
+    }
+
+    #[test]
+    fn test_memory_update() {
+        assert_near!(memory_update(0.0, &Some(Ok(2.5)), false), 2.5);
+        assert_near!(memory_update(2.5, &Some(Ok(1.0)), true), 1.5);
+        assert_near!(memory_update(1.5, &Some(Err("Error".to_string())), false), 1.5);
+        assert_near!(memory_update(1.5, &None, true), 1.5);
+        assert_near!(memory_update(1.5, &Some(Ok(f64::NAN)), false), 1.5);
+    }
+
+    #[test]
+    fn test_clear_confirmed() {
+        // Short expressions, or with the option off, clear straight away
+        assert!(clear_confirmed(5, false, true));
+        assert!(clear_confirmed(100, false, false));
+        // A long expression needs a second press
+        assert!(!clear_confirmed(100, false, true));
+        assert!(clear_confirmed(100, true, true));
+    }
+}
@@ -20,17 +20,62 @@
  *
  */
 
+use std::path::Path;
+
 use crate::evaluator::{AngleMode, Evaluator};
+use crate::evaluator::quantity::Quantity;
+use crate::evaluator::script::ScriptLine;
 
 #[derive(Debug, Default)]
 pub(crate) struct Calc {
     angle_mode: AngleMode,
+    quantities: bool,
+    decimals: bool,
+    durations: bool,
+    decimal_comma: bool,
+    integers: bool,
 }
 
 impl Calc {
 
-    pub(crate) fn evaluate(&mut self, expr: &str) -> Result<f64, String> {
-        Evaluator::with_mode(&self.angle_mode).evaluate(&expr)
+    pub(crate) fn evaluate(&mut self, expr: &str) -> Result<Quantity, String> {
+        self.evaluator().evaluate_quantity(&expr)
+    }
+
+    /// The result of an expression as it is being typed, if it has one yet.
+    pub(crate) fn preview(&self, expr: &str) -> Option<f64> {
+        self.evaluator().evaluate_preview(expr).ok().map(|q| q.value).filter(|v| v.is_finite())
+    }
+
+    /// Evaluate without recording the expression in history.
+    pub(crate) fn evaluate_preview(&self, expr: &str) -> Result<Quantity, String> {
+        self.evaluator().evaluate_preview(expr)
+    }
+
+    /// Evaluate a worksheet file with the current options.
+    pub(crate) fn run_file(&self, path: &Path) -> Result<Vec<ScriptLine>, String> {
+        self.evaluator().run_file(path)
+    }
+
+    pub(crate) fn to_latex(&self, expr: &str) -> Result<String, String> {
+        self.evaluator().to_latex(&expr)
+    }
+
+    pub(crate) fn is_complete(&self, expr: &str) -> bool {
+        self.evaluator().is_complete(&expr)
+    }
+
+    pub(crate) fn is_plausible_prefix(&self, expr: &str) -> bool {
+        self.evaluator().is_plausible_prefix(expr)
+    }
+
+    fn evaluator(&self) -> Evaluator {
+        Evaluator::with_mode(&self.angle_mode)
+            .with_quantities(self.quantities)
+            .with_decimals(self.decimals)
+            .with_durations(self.durations)
+            .with_decimal_comma(self.decimal_comma)
+            .with_integers(self.integers)
     }
 
     pub fn angle_mode(&self) -> &AngleMode {
@@ -39,4 +84,19 @@ impl Calc {
     pub fn set_angle_mode(&mut self, angle_mode: AngleMode) {
         self.angle_mode = angle_mode;
     }
+    pub fn set_quantities(&mut self, quantities: bool) {
+        self.quantities = quantities;
+    }
+    pub fn set_decimals(&mut self, decimals: bool) {
+        self.decimals = decimals;
+    }
+    pub fn set_durations(&mut self, durations: bool) {
+        self.durations = durations;
+    }
+    pub fn set_decimal_comma(&mut self, decimal_comma: bool) {
+        self.decimal_comma = decimal_comma;
+    }
+    pub fn set_integers(&mut self, integers: bool) {
+        self.integers = integers;
+    }
 }
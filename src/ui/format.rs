@@ -0,0 +1,824 @@
+/*
+ * Copyright (c) 2024.
+ *
+ * Copyright 2024 Trevor Campbell
+ *
+ * Permission is hereby granted, free of charge, to any person obtaining a copy of this software and
+ * associated documentation files (the “Software”), to deal in the Software without restriction,
+ * including without limitation the rights to use, copy, modify, merge, publish, distribute,
+ * sublicense, and/or sell copies of the Software, and to permit persons to whom the Software is
+ * furnished to do so, subject to the following conditions:
+ *
+ * The above copyright notice and this permission notice shall be included in all copies or
+ * substantial portions of the Software.
+ *
+ * THE SOFTWARE IS PROVIDED “AS IS”, WITHOUT WARRANTY OF ANY KIND, EXPRESS OR IMPLIED, INCLUDING BUT
+ * NOT LIMITED TO THE WARRANTIES OF MERCHANTABILITY, FITNESS FOR A PARTICULAR PURPOSE AND
+ * NONINFRINGEMENT. IN NO EVENT SHALL THE AUTHORS OR COPYRIGHT HOLDERS BE LIABLE FOR ANY CLAIM,
+ * DAMAGES OR OTHER LIABILITY, WHETHER IN AN ACTION OF CONTRACT, TORT OR OTHERWISE, ARISING FROM, OUT
+ * OF OR IN CONNECTION WITH THE SOFTWARE OR THE USE OR OTHER DEALINGS IN THE SOFTWARE.
+ *
+ */
+
+// Formatting of calculated and converted values for display.
+
+use crate::conversions::Unit;
+use crate::evaluator::constants::Constant;
+use crate::evaluator::quantity::Quantity;
+use crate::evaluator::script::ScriptLine;
+use crate::history::HistoryEntry;
+use crate::ui::preferences;
+
+pub(crate) static DEFAULT_DECIMAL_PLACES: usize = 10;
+/// Used to group thousands. We don't look up the locale, so this is always a comma.
+static GROUP_SEPARATOR: char = ',';
+
+/// How numbers are written. Auto uses scientific notation only for very large and small values.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub(crate) enum Notation {
+    #[default]
+    Auto,
+    Scientific,
+    Engineering,
+    Fixed,
+}
+
+impl Notation {
+    pub(crate) fn get_from_name(name: &str) -> Notation {
+        match name {
+            "scientific" => Notation::Scientific,
+            "engineering" => Notation::Engineering,
+            "fixed" => Notation::Fixed,
+            _ => Notation::Auto,
+        }
+    }
+}
+
+/// How results are formatted, usually read from preferences.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct FormatOptions {
+    pub(crate) decimal_places: usize,
+    /// Always show this many decimal places, keeping trailing zeros, e.g. `2.50`
+    pub(crate) fixed_decimals: Option<usize>,
+    pub(crate) group_digits: bool,
+    pub(crate) notation: Notation,
+    /// Write the decimal point as a comma, and group digits with a point, e.g. `1.234,5`
+    pub(crate) decimal_comma: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            decimal_places: DEFAULT_DECIMAL_PLACES,
+            fixed_decimals: None,
+            group_digits: false,
+            notation: Notation::Auto,
+            decimal_comma: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub(crate) fn from_preferences() -> Self {
+        let pref = preferences::manager();
+        Self {
+            decimal_places: decimal_places(),
+            fixed_decimals: pref.get::<usize>(preferences::FIXED_DECIMALS),
+            group_digits: pref.get::<bool>(preferences::GROUP_DIGITS).unwrap_or(false),
+            notation: pref.get::<String>(preferences::NUMBER_FORMAT)
+                .map(|name| Notation::get_from_name(&name))
+                .unwrap_or_default(),
+            decimal_comma: pref.get::<String>(preferences::DECIMAL_SEPARATOR).is_some_and(|s| s == ","),
+        }
+    }
+}
+
+/// The number of decimal places to show in results, from preferences.
+pub(crate) fn decimal_places() -> usize {
+    preferences::manager().get::<usize>(preferences::DECIMAL_PLACES)
+        .unwrap_or(DEFAULT_DECIMAL_PLACES)
+}
+
+/// Format a value for display using the formatting preferences.
+pub(crate) fn display_result(v: &f64) -> String {
+    format_with(v, &FormatOptions::from_preferences())
+}
+
+/// Units that can take an SI prefix, e.g. `m` can be `km` or `mm`.
+static SI_PREFIXABLE: [&str; 14] = ["m", "g", "s", "W", "J", "N", "B", "bit", "bit/s", "B/s", "L", "A", "V", "Ω"];
+static SI_PREFIX_POWERS: [(i32, &str); 9] = [(-12, "p"), (-9, "n"), (-6, "µ"), (-3, "m"), (0, ""),
+    (3, "k"), (6, "M"), (9, "G"), (12, "T")];
+
+/// Format a value in a unit with the SI prefix that keeps it between 1 and 1000, e.g. 1500 m
+/// is `1.5 km`. Values already in that range, or in units that don't take a prefix, are left alone.
+/// The number is formatted with `options`, but never in scientific notation as it is already scaled.
+pub(crate) fn format_si(v: f64, symbol: &str, options: &FormatOptions) -> Option<String> {
+    if !SI_PREFIXABLE.contains(&symbol) || v == 0.0 || !v.is_finite() || (1.0..1000.0).contains(&v.abs()) {
+        return None;
+    }
+    let exponent = ((v.abs().log10() / 3.0).floor() as i32 * 3).clamp(-12, 12);
+    let prefix = SI_PREFIX_POWERS.iter().find(|(e, _)| *e == exponent).map(|(_, p)| *p)?;
+    let mantissa = v / 10f64.powi(exponent);
+    let formatted = format_with(&mantissa, &FormatOptions { notation: Notation::Fixed, ..*options });
+    Some(format!("{} {}{}", formatted.trim_start_matches("= "), prefix, symbol))
+}
+
+/// The label for a unit in the conversion menus, e.g. `Kilometre (km)`.
+pub(crate) fn unit_label(unit: &Unit) -> String {
+    if unit.symbol.is_empty() || unit.symbol == unit.name {
+        unit.name.to_string()
+    } else {
+        format!("{} ({})", unit.name, unit.symbol)
+    }
+}
+
+/// Format a converted value for display. Inches are also shown to the nearest 1/16, which is
+/// what a tape measure reads. Optionally, metric values are shown with an SI prefix, e.g. `1.5 km`.
+pub(crate) fn display_converted(v: &f64, unit: Option<&Unit>) -> String {
+    if preferences::manager().get::<bool>(preferences::SI_PREFIXES).unwrap_or(false) {
+        if let Some(si) = unit.and_then(|unit| format_si(*v, unit.symbol, &FormatOptions::from_preferences())) {
+            return format!("= {}", si);
+        }
+    }
+    match unit {
+        Some(unit) if unit.symbol == "in" && v.is_finite() => {
+            format!("{} ≈ {}\"", display_result(v), to_imperial_fraction(*v, 16))
+        }
+        _ => display_result(v),
+    }
+}
+
+/// The largest denominator tried when looking for a multiple of π.
+static PI_DENOMINATOR_MAX: i64 = 12;
+/// How close a value must be to a multiple of π to be shown as one.
+static PI_TOLERANCE: f64 = 1e-4;
+
+/// Recognise a value that is a simple fraction of π, e.g. 1.5708 is `π/2` and -2.3562 is `-3π/4`.
+/// Denominators are tried smallest first so the fraction is in its lowest terms.
+pub(crate) fn as_pi_multiple(value: f64) -> Option<String> {
+    if !value.is_finite() || value.abs() < PI_TOLERANCE {
+        return None;
+    }
+    let ratio = value / std::f64::consts::PI;
+    (1..=PI_DENOMINATOR_MAX).find_map(|d| {
+        let n = (ratio * d as f64).round();
+        if n.abs() > (PI_DENOMINATOR_MAX * PI_DENOMINATOR_MAX) as f64
+            || (value - n * std::f64::consts::PI / d as f64).abs() > PI_TOLERANCE {
+            return None;
+        }
+        let numerator = match n as i64 {
+            1 => "π".to_string(),
+            -1 => "-π".to_string(),
+            n => format!("{}π", n),
+        };
+        Some(if d == 1 { numerator } else { format!("{}/{}", numerator, d) })
+    })
+}
+
+/// Round a value to the nearest fraction with the given denominator and show it as a whole
+/// number and a reduced fraction, e.g. `3 5/16`.
+pub(crate) fn to_imperial_fraction(inches: f64, denom: u32) -> String {
+    let denom = denom.max(1) as u64;
+    let parts = (inches.abs() * denom as f64).round() as u64;
+    let (whole, mut numerator) = (parts / denom, parts % denom);
+    let mut denominator = denom;
+    while numerator > 0 && numerator % 2 == 0 && denominator % 2 == 0 {
+        numerator /= 2;
+        denominator /= 2;
+    }
+    let sign = if inches < 0.0 && parts > 0 { "-" } else { "" };
+    match (whole, numerator) {
+        (w, 0) => format!("{}{}", sign, w),
+        (0, n) => format!("{}{}/{}", sign, n, denominator),
+        (w, n) => format!("{}{} {}/{}", sign, w, n, denominator),
+    }
+}
+
+/// Format a value for display with up to `decimal_places` decimal places.
+pub(crate) fn format_result(v: &f64, decimal_places: usize) -> String {
+    format_with(v, &FormatOptions { decimal_places, ..FormatOptions::default() })
+}
+
+/// Format a value for display. By default very large and very small values are shown in scientific
+/// notation, everything else as a decimal. Both the primary result and converted values are
+/// formatted here so they always look the same.
+pub(crate) fn format_with(v: &f64, options: &FormatOptions) -> String {
+    let scientific = match options.notation {
+        Notation::Auto => v.abs() < 0.001 || v.abs() > 10000000.0,
+        Notation::Scientific => true,
+        Notation::Engineering => return format!("= {}", swap_separators(format_engineering(*v, options), options)),
+        Notation::Fixed => false,
+    };
+    if scientific {
+        let formatted = format!("{:+.*e}", options.fixed_decimals.unwrap_or(options.decimal_places), v);
+        format!("= {}", swap_separators(trim_mantissa(&formatted, options), options))
+    } else {
+        let mut formatted = format!("{0:.1$}", v, options.fixed_decimals.unwrap_or(options.decimal_places));
+        if formatted.contains('.') && options.fixed_decimals.is_none() {
+            formatted = formatted.trim_end_matches('0').trim_end_matches('.').to_string();
+        }
+        if options.group_digits {
+            formatted = group_thousands(&formatted, GROUP_SEPARATOR);
+        }
+        format!("= {}", swap_separators(formatted, options))
+    }
+}
+
+/// Swap the decimal point and the group separator, if the decimal point is a comma.
+fn swap_separators(formatted: String, options: &FormatOptions) -> String {
+    if !options.decimal_comma {
+        return formatted;
+    }
+    formatted.chars()
+        .map(|c| match c {
+            '.' => ',',
+            ',' => '.',
+            c => c,
+        })
+        .collect()
+}
+
+/// Remove the trailing zeros of the mantissa of a number in scientific notation, e.g. `1.500e3`
+/// is `1.5e3`, unless a fixed number of decimal places is wanted.
+fn trim_mantissa(formatted: &str, options: &FormatOptions) -> String {
+    match formatted.split_once('e') {
+        Some((mantissa, exponent)) if mantissa.contains('.') && options.fixed_decimals.is_none() => {
+            format!("{}e{}", mantissa.trim_end_matches('0').trim_end_matches('.'), exponent)
+        }
+        _ => formatted.to_string(),
+    }
+}
+
+/// Format in engineering notation, like scientific notation but with the exponent always a multiple
+/// of 3, so it matches the SI prefixes, e.g. `12.345e3`. The decimal places are those of `options`.
+pub(crate) fn format_engineering(v: f64, options: &FormatOptions) -> String {
+    if v == 0.0 || !v.is_finite() {
+        return v.to_string();
+    }
+    let decimals = options.fixed_decimals.unwrap_or(options.decimal_places);
+    let exponent_of = |s: &str| s.split_once('e').map_or(0, |(_, e)| e.parse::<i32>().unwrap_or(0));
+    // Work on the digits of the scientific form, rounded to the decimal places wanted once the
+    // point has moved. Rounding can carry into the next power of ten, e.g. 999.96 to 1.0e3, which
+    // moves the point again.
+    let mut shift = exponent_of(&format!("{:e}", v.abs())).rem_euclid(3) as usize;
+    let mut scientific = format!("{:.*e}", shift + decimals, v.abs());
+    let exponent = exponent_of(&scientific);
+    if exponent.rem_euclid(3) as usize != shift {
+        shift = exponent.rem_euclid(3) as usize;
+        scientific = format!("{:.*e}", shift + decimals, v.abs());
+    }
+    let mantissa = scientific.split_once('e').map_or(scientific.as_str(), |(m, _)| m);
+    let digits = mantissa.replace('.', "");
+    let (whole, fraction) = digits.split_at(shift + 1);
+    let fraction = if options.fixed_decimals.is_some() { fraction } else { fraction.trim_end_matches('0') };
+    let sign = if v < 0.0 { "-" } else { "" };
+    if fraction.is_empty() {
+        format!("{}{}e{}", sign, whole, exponent - shift as i32)
+    } else {
+        format!("{}{}.{}e{}", sign, whole, fraction, exponent - shift as i32)
+    }
+}
+
+/// Format decimal degrees as degrees, minutes and seconds, e.g. `12°30'15"`.
+/// Seconds are rounded to hundredths.
+pub(crate) fn format_dms(degrees: f64) -> String {
+    let hundredths = (degrees.abs() * 360000.0).round() as u64;
+    let sign = if degrees < 0.0 && hundredths > 0 { "-" } else { "" };
+    format!("{}{}°{}'{}\"", sign, hundredths / 360000, (hundredths % 360000) / 6000,
+            (hundredths % 6000) as f64 / 100.0)
+}
+
+/// Format seconds as hours, minutes and seconds, e.g. `2:15:00`. Seconds are rounded to
+/// hundredths, which are only shown if there are any.
+pub(crate) fn format_duration(seconds: f64) -> String {
+    let hundredths = (seconds.abs() * 100.0).round() as u64;
+    let sign = if seconds < 0.0 && hundredths > 0 { "-" } else { "" };
+    let fraction = match hundredths % 100 {
+        0 => String::new(),
+        h => format!(".{:02}", h),
+    };
+    format!("{}{}:{:02}:{:02}{}", sign, hundredths / 360000, (hundredths % 360000) / 6000,
+            (hundredths % 6000) / 100, fraction)
+}
+
+/// Describe where a result from history came from, e.g. `Degrees, 2024-06-01 14:05 UTC`.
+/// Entries from older history files may have no mode or time.
+pub(crate) fn history_metadata(entry: &HistoryEntry) -> String {
+    let time = (entry.timestamp > 0).then(|| format_timestamp(entry.timestamp));
+    entry.mode.iter().cloned().chain(time).collect::<Vec<String>>().join(", ")
+}
+
+/// Format seconds since the Unix epoch as a UTC date and time, e.g. `2024-06-01 14:05 UTC`.
+fn format_timestamp(seconds: u64) -> String {
+    let days = seconds / 86400;
+    let minutes = (seconds % 86400) / 60;
+    // Convert days to a civil date, from Howard Hinnant's algorithm
+    let z = days + 719468;
+    let era = z / 146097;
+    let doe = z % 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    format!("{:04}-{:02}-{:02} {:02}:{:02} UTC", year, month, day, minutes / 60, minutes % 60)
+}
+
+/// Insert a separator between each group of three digits in the whole number part.
+fn group_thousands(number: &str, separator: char) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(rest) => ("-", rest),
+        None => ("", number),
+    };
+    let (whole, fraction) = match unsigned.find('.') {
+        Some(i) => unsigned.split_at(i),
+        None => (unsigned, ""),
+    };
+    let mut grouped = String::new();
+    for (i, c) in whole.chars().enumerate() {
+        if i > 0 && (whole.len() - i) % 3 == 0 {
+            grouped.push(separator);
+        }
+        grouped.push(c);
+    }
+    format!("{}{}{}", sign, grouped, fraction)
+}
+
+/// The ways a result can be copied to the clipboard.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum CopyFormat {
+    Plain,
+    Scientific,
+    Grouped,
+    Latex,
+}
+
+/// Build the text to copy for a result. Scientific and grouped copies are formatted like the
+/// result, with its decimal places and separators, but without the sign of positive values. LaTeX includes the expression, if it can be
+/// written.
+pub(crate) fn copy_text(format: CopyFormat, value: f64, latex: Option<&str>, options: &FormatOptions) -> String {
+    let formatted = |options: FormatOptions| format_with(&value, &options).trim_start_matches("= ").trim_start_matches('+').to_string();
+    match format {
+        CopyFormat::Plain => value.to_string(),
+        CopyFormat::Scientific => formatted(FormatOptions { notation: Notation::Scientific, ..*options }),
+        CopyFormat::Grouped => formatted(FormatOptions { notation: Notation::Fixed, group_digits: true, ..*options }),
+        CopyFormat::Latex => match latex {
+            Some(expression) => format!("{} = {}", expression, value),
+            None => value.to_string(),
+        },
+    }
+}
+
+/// Build the text to insert a value into an expression, with its unit symbol if it has one,
+/// so the calculation can carry on from it.
+pub(crate) fn insertion_text(value: &f64, unit: Option<&Unit>) -> String {
+    match unit {
+        Some(unit) => format!("{} {}", value, unit.symbol),
+        None => value.to_string(),
+    }
+}
+
+/// The text to replace an expression with once it has been evaluated, so the next calculation
+/// starts from the result. Results that can't be written back, like infinity, give None.
+pub(crate) fn result_expression(result: &Quantity, with_unit: bool) -> Option<String> {
+    let unit = if with_unit { result.unit } else { None };
+    result.value.is_finite().then(|| insertion_text(&result.value, unit))
+}
+
+/// Build the text to insert a constant, either its symbol or its value. Very large and very small
+/// values are written in scientific notation to keep them short.
+pub(crate) fn constant_text(constant: &Constant, as_value: bool) -> String {
+    let value = constant.value;
+    if !as_value {
+        constant.name().to_string()
+    } else if value != 0.0 && (value.abs() < 0.001 || value.abs() >= 1e7) {
+        format!("{:e}", value)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Build the text to multiply by a negative power of ten, `10⁻ⁿ`.
+/// A negative `n` gives a positive power.
+pub(crate) fn power_of_ten_text(n: i32) -> String {
+    if n > 0 {
+        format!("*10^(-{})", n)
+    } else {
+        format!("*10^{}", -n)
+    }
+}
+
+/// Write a power of ten with a superscript exponent, e.g. `×10⁻³`.
+pub(crate) fn power_of_ten_label(power: i32) -> String {
+    let superscript: String = power.to_string().chars()
+        .map(|c| match c {
+            '-' => '⁻',
+            '0' => '⁰',
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '4' => '⁴',
+            '5' => '⁵',
+            '6' => '⁶',
+            '7' => '⁷',
+            '8' => '⁸',
+            _ => '⁹',
+        })
+        .collect();
+    format!("×10{}", superscript)
+}
+
+static ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+    "ten", "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+static TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+static SCALES: [&str; 4] = ["", "thousand", "million", "billion"];
+
+/// Spell a value out in words, e.g. `one thousand two hundred thirty-four point five`.
+/// The decimal tail is read digit by digit. Returns None for values too large to spell out.
+pub(crate) fn number_to_words(value: f64) -> Option<String> {
+    if !value.is_finite() || value.abs() >= 1e12 {
+        return None;
+    }
+    let digits = format!("{0:.1$}", value.abs(), DEFAULT_DECIMAL_PLACES);
+    let (whole, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+    let fraction = fraction.trim_end_matches('0');
+    let mut whole: u64 = whole.parse().ok()?;
+
+    let mut words = Vec::new();
+    if whole == 0 {
+        words.push(ONES[0].to_string());
+    }
+    let mut groups = Vec::new();
+    for scale in SCALES {
+        if whole % 1000 != 0 {
+            let group = hundreds_to_words(whole % 1000);
+            groups.push(if scale.is_empty() { group } else { format!("{} {}", group, scale) });
+        }
+        whole /= 1000;
+    }
+    words.extend(groups.into_iter().rev());
+    if !fraction.is_empty() {
+        words.push("point".to_string());
+        words.extend(fraction.chars()
+            .filter_map(|c| c.to_digit(10))
+            .map(|d| ONES[d as usize].to_string()));
+    }
+    let sign = if value < 0.0 && words.iter().any(|w| w != ONES[0] && w != "point") { "minus " } else { "" };
+    Some(format!("{}{}", sign, words.join(" ")))
+}
+
+/// The result line with the expression in front of the result, e.g. `2 * 3 = 6`.
+pub(crate) fn result_line(expr: &str, formatted: &str) -> String {
+    let result = formatted.strip_prefix("= ").unwrap_or(formatted);
+    format!("{} = {}", expr.trim(), result)
+}
+
+/// The lines of a worksheet each with its result, or why it has none, e.g. `rate * 2 = 0.14`.
+pub(crate) fn worksheet_text(lines: &[ScriptLine], options: &FormatOptions) -> String {
+    lines.iter()
+        .map(|line| match &line.result {
+            Ok(value) => result_line(&line.line, &format_with(value, options)),
+            Err(e) => format!("{}: {}", line.line, e),
+        })
+        .map(|line| line + "\n")
+        .collect()
+}
+
+/// A conversion as a sentence to share, e.g. `5 Kilometre = 3.10686 Mile`.
+pub(crate) fn conversion_sentence(input: f64, from: &Unit, output: f64, to: &Unit, options: &FormatOptions) -> String {
+    let value = |v: &f64| format_with(v, options).trim_start_matches("= ").to_string();
+    format!("{} {} = {} {}", value(&input), from.name, value(&output), to.name)
+}
+
+/// Describe a calculation in words for a screen reader, e.g. `2 * 3.5` is
+/// `two times three point five equals seven`. Names of functions are kept as they are.
+pub(crate) fn describe_expression_and_result(expr: &str, result: f64) -> String {
+    let chars: Vec<char> = expr.trim().chars().collect();
+    let mut words = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let start = i;
+        let c = chars[i];
+        if c.is_ascii_digit() || c == '.' {
+            while i < chars.len() && (chars[i].is_ascii_digit() || chars[i] == '.') {
+                i += 1;
+            }
+            let number: String = chars[start..i].iter().collect();
+            words.push(number.parse::<f64>().ok()
+                .and_then(number_to_words)
+                .unwrap_or(number));
+            continue;
+        }
+        if c.is_alphabetic() && c != 'π' {
+            while i < chars.len() && chars[i].is_alphanumeric() {
+                i += 1;
+            }
+            words.push(chars[start..i].iter().collect());
+            continue;
+        }
+        let word = match c {
+            '+' => "plus",
+            '-' => "minus",
+            '*' | '×' => "times",
+            '/' | '÷' => "divided by",
+            '^' => "to the power of",
+            '%' => "percent",
+            '(' => "open bracket",
+            ')' => "close bracket",
+            ',' => "comma",
+            '√' => "square root of",
+            'π' => "pi",
+            _ => "",
+        };
+        if !word.is_empty() {
+            words.push(word.to_string());
+        }
+        i += 1;
+    }
+    words.push("equals".to_string());
+    words.push(number_to_words(result).unwrap_or_else(|| result.to_string()));
+    words.join(" ")
+}
+
+/// Words for a whole number below one thousand.
+fn hundreds_to_words(n: u64) -> String {
+    let mut words = Vec::new();
+    if n >= 100 {
+        words.push(format!("{} hundred", ONES[(n / 100) as usize]));
+    }
+    let rest = (n % 100) as usize;
+    if rest >= 20 {
+        words.push(match rest % 10 {
+            0 => TENS[rest / 10].to_string(),
+            d => format!("{}-{}", TENS[rest / 10], ONES[d]),
+        });
+    } else if rest > 0 {
+        words.push(ONES[rest].to_string());
+    }
+    words.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::conversions::convert;
+    use crate::conversions::length::{FOOT, KILOMETRE, LIGHTYEAR, METRE, MILE, PARSEC};
+    use crate::evaluator::constants::{C, G, Pi};
+    use crate::evaluator::quantity::Quantity;
+    use crate::evaluator::script::ScriptLine;
+    use crate::history::HistoryEntry;
+    use crate::ui::format::{as_pi_multiple, constant_text, conversion_sentence, copy_text, describe_expression_and_result, CopyFormat, format_engineering, format_result, format_with, FormatOptions, format_dms, format_duration, format_si, history_metadata, insertion_text, Notation, number_to_words, power_of_ten_label, power_of_ten_text, result_expression, result_line, to_imperial_fraction, unit_label, worksheet_text};
+
+    #[test]
+    fn test_format_result() {
+        assert_eq!(format_result(&4.0, 10), "= 4");
+        assert_eq!(format_result(&3.25, 10), "= 3.25");
+        assert_eq!(format_result(&12345678.0, 10), "= +1.2345678e7");
+        assert_eq!(format_result(&0.0001, 10), "= +1e-4");
+    }
+
+    #[test]
+    fn test_decimal_places() {
+        assert_eq!(format_result(&3.14159, 2), "= 3.14");
+        assert_eq!(format_result(&3.14159, 0), "= 3");
+        assert_eq!(format_result(&2.5, 4), "= 2.5");
+        assert_eq!(format_result(&100.0, 0), "= 100");
+    }
+
+    #[test]
+    fn test_fixed_decimals() {
+        let fixed = FormatOptions { fixed_decimals: Some(2), ..FormatOptions::default() };
+        assert_eq!(format_with(&2.5, &fixed), "= 2.50");
+        assert_eq!(format_with(&2.0, &fixed), "= 2.00");
+        assert_eq!(format_with(&2.005, &fixed), "= 2.00");
+        assert_eq!(format_with(&1234.5, &FormatOptions { group_digits: true, ..fixed }), "= 1,234.50");
+        assert_eq!(format_with(&2.5, &FormatOptions::default()), "= 2.5");
+    }
+
+    #[test]
+    fn test_group_digits() {
+        let comma = FormatOptions { decimal_comma: true, group_digits: true, ..FormatOptions::default() };
+        assert_eq!(format_with(&1234567.25, &comma), "= 1.234.567,25");
+        assert_eq!(format_with(&3.14, &FormatOptions { decimal_comma: true, ..FormatOptions::default() }), "= 3,14");
+        let grouped = FormatOptions { group_digits: true, ..FormatOptions::default() };
+        assert_eq!(format_with(&1234567.5, &grouped), "= 1,234,567.5");
+        assert_eq!(format_with(&1234567.5, &FormatOptions::default()), "= 1234567.5");
+        assert_eq!(format_with(&-123456.0, &grouped), "= -123,456");
+        assert_eq!(format_with(&999.25, &grouped), "= 999.25");
+        assert_eq!(format_with(&12345678.0, &grouped), "= +1.2345678e7");
+    }
+
+    #[test]
+    fn test_format_engineering() {
+        assert_eq!(format_engineering(12345.0, &FormatOptions::default()), "12.345e3");
+        assert_eq!(format_engineering(0.0001234, &FormatOptions::default()), "123.4e-6");
+        assert_eq!(format_engineering(1000.0, &FormatOptions::default()), "1e3");
+        assert_eq!(format_engineering(100.0, &FormatOptions::default()), "100e0");
+        assert_eq!(format_engineering(-0.05, &FormatOptions::default()), "-50e-3");
+        assert_eq!(format_engineering(0.0, &FormatOptions::default()), "0");
+    }
+
+    #[test]
+    fn test_format_dms() {
+        assert_eq!(format_dms(12.5), "12°30'0\"");
+        assert_eq!(format_dms(12.504166667), "12°30'15\"");
+        assert_eq!(format_dms(-0.25), "-0°15'0\"");
+        assert_eq!(format_dms(1.0 / 7200.0), "0°0'0.5\"");
+        assert_eq!(format_dms(359.999999), "360°0'0\"");
+    }
+
+    #[test]
+    fn test_notation() {
+        let options = |notation| FormatOptions { notation, ..FormatOptions::default() };
+        assert_eq!(format_with(&12345.0, &options(Notation::Auto)), "= 12345");
+        assert_eq!(format_with(&12345.0, &options(Notation::Scientific)), "= +1.2345e4");
+        assert_eq!(format_with(&12345.0, &options(Notation::Engineering)), "= 12.345e3");
+        assert_eq!(format_with(&12345678.0, &options(Notation::Fixed)), "= 12345678");
+        let options = |notation| FormatOptions { notation, decimal_places: 2, ..FormatOptions::default() };
+        assert_eq!(format_with(&12345.678, &options(Notation::Scientific)), "= +1.23e4");
+        assert_eq!(format_with(&12345.678, &options(Notation::Engineering)), "= 12.35e3");
+        assert_eq!(format_with(&12000.0, &options(Notation::Engineering)), "= 12e3");
+        assert_eq!(format_with(&999.996, &options(Notation::Engineering)), "= 1e3");
+        let fixed = |notation| FormatOptions { notation, fixed_decimals: Some(2), ..FormatOptions::default() };
+        assert_eq!(format_with(&12000.0, &fixed(Notation::Scientific)), "= +1.20e4");
+        assert_eq!(format_with(&12000.0, &fixed(Notation::Engineering)), "= 12.00e3");
+        assert_eq!(Notation::get_from_name("engineering"), Notation::Engineering);
+        assert_eq!(Notation::get_from_name("nonsense"), Notation::Auto);
+    }
+
+    #[test]
+    fn test_format_converted() {
+        let metres = convert(&1.0, &LIGHTYEAR, &METRE);
+        assert_eq!(format_result(&metres, 10), "= +9.4607304726e15");
+        let kilometres = convert(&1.0, &PARSEC, &KILOMETRE);
+        assert!(format_result(&kilometres, 10).starts_with("= +3.0856775815"));
+        assert!(format_result(&kilometres, 10).ends_with("e13"));
+        let lightyears = convert(&1.0, &METRE, &LIGHTYEAR);
+        assert!(format_result(&lightyears, 10).ends_with("e-16"));
+    }
+
+    #[test]
+    fn test_insertion_text() {
+        assert_eq!(insertion_text(&1.5, None), "1.5");
+        assert_eq!(insertion_text(&-2.0, None), "-2");
+        assert_eq!(insertion_text(&1.5, Some(&KILOMETRE)), "1.5 km");
+        let feet = convert(&1.0, &METRE, &FOOT);
+        assert_eq!(insertion_text(&feet, Some(&FOOT)), format!("{} ft", feet));
+    }
+
+    #[test]
+    fn test_result_expression() {
+        assert_eq!(result_expression(&Quantity::number(42.0), false).as_deref(), Some("42"));
+        assert_eq!(result_expression(&Quantity::number(-0.5), true).as_deref(), Some("-0.5"));
+        let distance = Quantity::with_unit(1.5, &KILOMETRE);
+        assert_eq!(result_expression(&distance, true).as_deref(), Some("1.5 km"));
+        assert_eq!(result_expression(&distance, false).as_deref(), Some("1.5"));
+        assert_eq!(result_expression(&Quantity::number(f64::INFINITY), false), None);
+        assert_eq!(result_expression(&Quantity::number(f64::NAN), false), None);
+    }
+
+    #[test]
+    fn test_power_of_ten() {
+        assert_eq!(power_of_ten_text(3), "*10^(-3)");
+        assert_eq!(power_of_ten_text(-6), "*10^6");
+        assert_eq!(power_of_ten_text(0), "*10^0");
+        assert_eq!(power_of_ten_label(-12), "×10⁻¹²");
+        assert_eq!(power_of_ten_label(9), "×10⁹");
+    }
+
+    #[test]
+    fn test_copy_text() {
+        let options = FormatOptions::default();
+        assert_eq!(copy_text(CopyFormat::Plain, 1234567.5, None, &options), "1234567.5");
+        assert_eq!(copy_text(CopyFormat::Scientific, 1234567.5, None, &options), "1.2345675e6");
+        assert_eq!(copy_text(CopyFormat::Grouped, 1234567.5, None, &options), "1,234,567.5");
+        assert_eq!(copy_text(CopyFormat::Grouped, -1234.0, None, &options), "-1,234");
+        assert_eq!(copy_text(CopyFormat::Latex, 0.5, Some("\\frac{1}{2}"), &options), "\\frac{1}{2} = 0.5");
+        assert_eq!(copy_text(CopyFormat::Latex, 0.5, None, &options), "0.5");
+        let options = FormatOptions { decimal_places: 2, decimal_comma: true, ..FormatOptions::default() };
+        assert_eq!(copy_text(CopyFormat::Scientific, 1234567.5, None, &options), "1,23e6");
+        assert_eq!(copy_text(CopyFormat::Grouped, 1234567.456, None, &options), "1.234.567,46");
+        let options = FormatOptions { fixed_decimals: Some(2), ..FormatOptions::default() };
+        assert_eq!(copy_text(CopyFormat::Grouped, 1234.5, None, &options), "1,234.50");
+    }
+
+    #[test]
+    fn test_number_to_words() {
+        assert_eq!(number_to_words(1234.0).as_deref(), Some("one thousand two hundred thirty-four"));
+        assert_eq!(number_to_words(0.0).as_deref(), Some("zero"));
+        assert_eq!(number_to_words(3.14).as_deref(), Some("three point one four"));
+        assert_eq!(number_to_words(-2_000_015.0).as_deref(), Some("minus two million fifteen"));
+        assert_eq!(number_to_words(1e12), None);
+    }
+
+    #[test]
+    fn test_imperial_fraction() {
+        assert_eq!(to_imperial_fraction(3.3125, 16), "3 5/16");
+        assert_eq!(to_imperial_fraction(3.25, 16), "3 1/4");
+        assert_eq!(to_imperial_fraction(0.5, 16), "1/2");
+        assert_eq!(to_imperial_fraction(2.999, 16), "3");
+        assert_eq!(to_imperial_fraction(-1.125, 8), "-1 1/8");
+    }
+
+    #[test]
+    fn test_constant_text() {
+        assert_eq!(constant_text(&Pi, false), "π");
+        assert_eq!(constant_text(&Pi, true), std::f64::consts::PI.to_string());
+        assert_eq!(constant_text(&C, true), "2.99792458e8");
+        assert_eq!(constant_text(&G, true), "6.674e-11");
+    }
+
+    #[test]
+    fn test_pi_multiple() {
+        assert_eq!(as_pi_multiple(1.5708).as_deref(), Some("π/2"));
+        assert_eq!(as_pi_multiple(std::f64::consts::PI).as_deref(), Some("π"));
+        assert_eq!(as_pi_multiple(-3.0 * std::f64::consts::FRAC_PI_4).as_deref(), Some("-3π/4"));
+        assert_eq!(as_pi_multiple(4.0 * std::f64::consts::PI / 6.0).as_deref(), Some("2π/3"));
+        assert_eq!(as_pi_multiple(2.0 * std::f64::consts::PI).as_deref(), Some("2π"));
+        assert_eq!(as_pi_multiple(1.5), None);
+        assert_eq!(as_pi_multiple(0.0), None);
+    }
+
+    #[test]
+    fn test_format_si() {
+        let options = FormatOptions::default();
+        assert_eq!(format_si(1.5e-3, "m", &options), Some("1.5 mm".to_string()));
+        assert_eq!(format_si(1500.0, "m", &options), Some("1.5 km".to_string()));
+        assert_eq!(format_si(-2.5e6, "W", &options), Some("-2.5 MW".to_string()));
+        assert_eq!(format_si(4.2e-7, "s", &options), Some("420 ns".to_string()));
+        assert_eq!(format_si(3.0e9, "B", &options), Some("3 GB".to_string()));
+        assert_eq!(format_si(12.0, "m", &options), None);
+        assert_eq!(format_si(1500.0, "ft", &options), None);
+        assert_eq!(format_si(1500.0, "km", &options), None);
+        assert_eq!(format_si(0.0, "m", &options), None);
+        let options = FormatOptions { decimal_places: 2, decimal_comma: true, notation: Notation::Scientific, ..options };
+        assert_eq!(format_si(1234.5, "m", &options), Some("1,23 km".to_string()));
+    }
+
+    #[test]
+    fn test_unit_label() {
+        assert_eq!(unit_label(&KILOMETRE), "Kilometre (km)");
+        assert_eq!(unit_label(&FOOT), "Foot (ft)");
+    }
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(8100.0), "2:15:00");
+        assert_eq!(format_duration(45.5), "0:00:45.50");
+        assert_eq!(format_duration(-3661.0), "-1:01:01");
+        assert_eq!(format_duration(100.0 * 3600.0), "100:00:00");
+    }
+
+    #[test]
+    fn test_history_metadata() {
+        let entry = |timestamp, mode: Option<&str>| HistoryEntry {
+            expr: "sin(30)".to_string(),
+            value: 0.5,
+            timestamp,
+            mode: mode.map(str::to_string),
+        };
+        assert_eq!(history_metadata(&entry(1717250700, Some("Degrees"))), "Degrees, 2024-06-01 14:05 UTC");
+        assert_eq!(history_metadata(&entry(0, Some("Radians"))), "Radians");
+        assert_eq!(history_metadata(&entry(951782400, None)), "2000-02-29 00:00 UTC");
+        assert_eq!(history_metadata(&entry(0, None)), "");
+    }
+
+    #[test]
+    fn test_result_line() {
+        assert_eq!(result_line("2 * 3", "= 6"), "2 * 3 = 6");
+        assert_eq!(result_line(" 1 km to m\n", "1000 m"), "1 km to m = 1000 m");
+    }
+
+    #[test]
+    fn test_worksheet_text() {
+        let lines = [
+            ScriptLine { line: "rate = 0.07".to_string(), result: Ok(0.07) },
+            ScriptLine { line: "rate * 2".to_string(), result: Ok(0.14) },
+            ScriptLine { line: "sin = 3".to_string(), result: Err("'sin' is a function".to_string()) },
+        ];
+        assert_eq!(worksheet_text(&lines, &FormatOptions::default()),
+                   "rate = 0.07 = 0.07\nrate * 2 = 0.14\nsin = 3: 'sin' is a function\n");
+        assert_eq!(worksheet_text(&[], &FormatOptions::default()), "");
+    }
+
+    #[test]
+    fn test_conversion_sentence() {
+        let options = FormatOptions { decimal_places: 5, ..Default::default() };
+        let miles = convert(&5.0, &KILOMETRE, &MILE);
+        assert_eq!(conversion_sentence(5.0, &KILOMETRE, miles, &MILE, &options), "5 Kilometre = 3.10686 Mile");
+        let options = FormatOptions { group_digits: true, decimal_comma: true, ..options };
+        assert_eq!(conversion_sentence(1500.5, &METRE, 1.5005, &KILOMETRE, &options), "1.500,5 Metre = 1,5005 Kilometre");
+    }
+
+    #[test]
+    fn test_describe_expression_and_result() {
+        assert_eq!(describe_expression_and_result("2 * 3.5", 7.0), "two times three point five equals seven");
+        assert_eq!(describe_expression_and_result("sqrt(16) - 1\n", 3.0),
+                   "sqrt open bracket sixteen close bracket minus one equals three");
+        assert_eq!(describe_expression_and_result("2π", 6.25), "two pi equals six point two five");
+    }
+}
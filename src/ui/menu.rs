@@ -24,36 +24,50 @@
 use iced::{alignment, Background, Border, Element, Length, Padding, Renderer, Theme};
 use iced::advanced::text::Shaping;
 use iced::border::Radius;
-use iced::widget::{Button, button, Container, row, text};
+use iced::widget::{Button, button, column, Container, row, text, text_input};
 use iced_aw::menu::{Item, Menu, primary};
 use iced_aw::{BOOTSTRAP_FONT, menu, menu_bar};
 use iced_aw::Bootstrap;
 use iced_aw::style::Status;
 use strum::IntoEnumIterator;
-use crate::{conversions, evaluator, history, ui};
+use crate::{bookmarks, conversions, evaluator, history, macros, recent, session, ui};
 use crate::conversions::{Dimension, Unit};
+use crate::history::Stats;
+use crate::ui::format::CopyFormat;
 use crate::ui::messages::Message;
 
+/// The number of recent results summarised in the history menu
+static STATS_COUNT: usize = 10;
+
 /// Builds the menus for our calculator
-pub(crate) fn build_menu_bar<'a> () -> Element<'a, Message> {
+pub(crate) fn build_menu_bar<'a> (history_filter: &str, recording: bool) -> Element<'a, Message> {
 
+    let edit_menu = menu_edit();
     let insert_menu = menu_insert();
     let convert_menu = menu_dimension();
     let theme_menu = menu_theme();
+    let options_menu = menu_options();
+    let bookmarks_menu = menu_bookmarks(recording);
 
     let mb=
-        if let Some(history_menu) = menu_history() {
+        if let Some(history_menu) = menu_history(history_filter) {
             menu_bar!(
+                (menu_top("Edit"), edit_menu)
                 (menu_top("Convert"), convert_menu)
                 (menu_top("Insert"), insert_menu)
                 (menu_top("History"), history_menu)
+                (menu_top("Bookmarks"), bookmarks_menu)
                 (menu_top("Theme"), theme_menu)
+                (menu_top("Options"), options_menu)
             )
         } else {
             menu_bar!(
+                (menu_top("Edit"), edit_menu)
                 (menu_top("Convert"), convert_menu)
                 (menu_top("Insert"), insert_menu)
+                (menu_top("Bookmarks"), bookmarks_menu)
                 (menu_top("Theme"), theme_menu)
+                (menu_top("Options"), options_menu)
             )
         };
 
@@ -88,12 +102,33 @@ fn menu_top(label: &str) -> Container<Message> {
     container
 }
 
+fn menu_edit() -> Menu<'static, Message, Theme, Renderer> {
+
+    let mut items = Vec::new();
+
+    items.push(Item::with_menu(menu_item_sub("Copy as".to_string(), Message::Null), menu_copy_as()));
+    items.push(Item::new(menu_item("Run file...".to_string(), Message::RunFile)));
+
+    Menu::new(items).offset(3.0).spacing(2.0).max_width(120.0)
+}
+
+fn menu_copy_as() -> Menu<'static, Message, Theme, Renderer> {
+
+    let mut items = Vec::new();
+    for (label, format) in [("Plain number", CopyFormat::Plain), ("Scientific", CopyFormat::Scientific),
+                            ("Grouped", CopyFormat::Grouped), ("LaTeX", CopyFormat::Latex)] {
+        items.push(Item::new(menu_item(label.to_string(), Message::CopyAs(format))));
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(120.0)
+}
+
 fn menu_insert() -> Menu<'static, Message, Theme, Renderer> {
 
     let mut items = Vec::new();
 
     items.push(Item::with_menu(menu_item("Constants".to_string(), Message::Null), menu_constants()));
     items.push(Item::with_menu(menu_item("Functions".to_string(), Message::Null), menu_functions()));
+    items.push(Item::with_menu(menu_item("×10⁻ⁿ".to_string(), Message::Null), menu_powers_of_ten()));
 
     Menu::new(items).offset(3.0).spacing(2.0).max_width(120.0)
 }
@@ -101,10 +136,32 @@ fn menu_insert() -> Menu<'static, Message, Theme, Renderer> {
 fn menu_constants() -> Menu<'static, Message, Theme, Renderer> {
 
     let mut items = Vec::new();
-    for c in evaluator::constants::get_all().iter() {
-        items.push(Item::new(menu_item(c.long_name().to_string(), Message::Constant(c.name().to_string()))));
+    for c in evaluator::constants::get_builtin().iter() {
+        let label = if c.units().is_empty() {
+            c.long_name().to_string()
+        } else {
+            format!("{} ({})", c.long_name(), c.units())
+        };
+        items.push(Item::new(menu_item(label, Message::Constant(c.name().to_string()))));
+    }
+    for c in evaluator::user_constants::manager().constants().iter() {
+        let label = format!("{} = {}", c.name(), c.value);
+        items.push(Item::new(menu_item_removable(label,
+                                                 Message::Constant(c.name().to_string()),
+                                                 Message::RemoveConstant(c.name().to_string()))));
+    }
+    items.push(Item::new(menu_item("Define constant (name = value)".to_string(), Message::AddConstant)));
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(200.0)
+}
+
+fn menu_powers_of_ten() -> Menu<'static, Message, Theme, Renderer> {
+
+    let mut items = Vec::new();
+    for n in [1, 2, 3, 6, 9, 12, 15, 18] {
+        items.push(Item::new(menu_item(ui::format::power_of_ten_label(-n),
+                                       Message::Char(ui::format::power_of_ten_text(n)))));
     }
-    Menu::new(items).offset(0.0).spacing(2.0).max_width(150.0)
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(75.0)
 }
 
 fn menu_functions() -> Menu<'static, Message, Theme, Renderer> {
@@ -114,26 +171,83 @@ fn menu_functions() -> Menu<'static, Message, Theme, Renderer> {
     for f in evaluator::functions::get_all().iter() {
         items.push(Item::new(menu_item(f.name().to_string(), Message::Func(f.name().to_string()))));
     }
+    for f in evaluator::functions::get_all_multi().iter() {
+        items.push(Item::new(menu_item(f.name().to_string(), Message::Func(f.name().to_string()))));
+    }
 
     Menu::new(items).offset(0.0).spacing(2.0).max_width(75.0)
 
 }
-fn menu_history() -> Option<Menu<'static, Message, Theme, Renderer>> {
+fn menu_history(filter: &str) -> Option<Menu<'static, Message, Theme, Renderer>> {
 
     if let Ok(queue) = history::manager().history().entries().read().as_deref() {
         if queue.len() == 0 {
-            None
-        } else {
-            let mut items = Vec::new();
-            for history_item in queue.iter() {
-                items.push(Item::new(menu_item(history_item.0.clone(),
-                                               Message::History(history_item.0.clone(), history_item.1.clone()))));
-            }
-            Some(Menu::new(items).offset(0.0).spacing(2.0).max_width(200.0))
+            return None;
         }
+    }
+
+    let mut items = Vec::new();
+    items.push(Item::new(text_input("Search history", filter)
+        .on_input(Message::HistoryFilter)
+        .padding(Padding::from([2, 4]))));
+    if let Some(stats) = history::manager().history().stats(STATS_COUNT) {
+        let label = format!("Last {} results", stats.count);
+        items.push(Item::with_menu(menu_item_sub(label, Message::Null), menu_stats(&stats)));
+    }
+    for entry in history::manager().history().matching(filter) {
+        let metadata = ui::format::history_metadata(&entry);
+        items.push(Item::new(menu_item_detail(entry.expr.clone(), metadata, Message::History(entry.expr, entry.value))));
+    }
+    Some(Menu::new(items).offset(0.0).spacing(2.0).max_width(200.0))
+}
+
+/// Statistics of the recent results, each of which can be copied.
+fn menu_stats(stats: &Stats) -> Menu<'static, Message, Theme, Renderer> {
+
+    let mut items = Vec::new();
+    for (label, value) in [("Sum", stats.sum), ("Mean", stats.mean), ("Min", stats.min), ("Max", stats.max)] {
+        items.push(Item::new(menu_item_detail(label.to_string(), ui::format::display_result(&value), Message::Copy(value))));
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(160.0)
+}
+
+fn menu_bookmarks(recording: bool) -> Menu<'static, Message, Theme, Renderer> {
+
+    let mut items = Vec::new();
+    items.push(Item::new(menu_item("Bookmark expression".to_string(), Message::AddBookmark)));
+    if recording {
+        items.push(Item::new(menu_item("Stop recording macro".to_string(), Message::StopRecording)));
     } else {
-        None
+        items.push(Item::new(menu_item("Record macro".to_string(), Message::StartRecording)));
     }
+    if let Some(macros_menu) = menu_macros() {
+        items.push(Item::with_menu(menu_item_sub("Macros".to_string(), Message::Null), macros_menu));
+    }
+
+    if let Ok(bookmarks) = bookmarks::manager().bookmarks().entries().read().as_deref() {
+        for bookmark in bookmarks.iter() {
+            items.push(Item::new(menu_item_removable(bookmark.clone(),
+                                                     Message::Bookmark(bookmark.clone()),
+                                                     Message::RemoveBookmark(bookmark.clone()))));
+        }
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(200.0)
+}
+
+fn menu_macros() -> Option<Menu<'static, Message, Theme, Renderer>> {
+
+    let mut items = Vec::new();
+    if let Ok(macros) = macros::manager().macros().entries().read().as_deref() {
+        for m in macros.iter() {
+            items.push(Item::new(menu_item_removable(m.name.clone(),
+                                                     Message::RunMacro(m.name.clone()),
+                                                     Message::RemoveMacro(m.name.clone()))));
+        }
+    }
+    if items.is_empty() {
+        return None;
+    }
+    Some(Menu::new(items).offset(0.0).spacing(2.0).max_width(160.0))
 }
 
 fn menu_theme() -> Menu<'static, Message, Theme, Renderer> {
@@ -154,39 +268,251 @@ fn menu_theme() -> Menu<'static, Message, Theme, Renderer> {
 
 }
 
+fn menu_options() -> Menu<'static, Message, Theme, Renderer> {
+
+    let options = vec![
+        ("Auto evaluate", ui::preferences::AUTO_EVALUATE),
+        ("Units in expressions", ui::preferences::QUANTITIES),
+        ("Decimal arithmetic", ui::preferences::DECIMAL_MODE),
+        ("Integer arithmetic", ui::preferences::INTEGER_MODE),
+        ("Durations (h:m:s)", ui::preferences::DURATIONS),
+        ("Group digits", ui::preferences::GROUP_DIGITS),
+        ("Degrees, minutes, seconds", ui::preferences::DMS_OUTPUT),
+        ("Multiples of π", ui::preferences::PI_MULTIPLES),
+        ("Expression with result", ui::preferences::SHOW_EXPRESSION),
+        ("Continue from result", ui::preferences::RESULT_AS_EXPRESSION),
+        ("Result in words", ui::preferences::NUMBER_WORDS),
+        ("Flag invalid input", ui::preferences::VALIDATE_INPUT),
+        ("Require closing parentheses", ui::preferences::STRICT_PARENS),
+        ("Insert constant values", ui::preferences::CONSTANT_VALUES),
+        ("Confirm clearing long expressions", ui::preferences::CONFIRM_CLEAR),
+        ("Start in conversion mode", ui::preferences::START_IN_CONVERT),
+        ("Constant buttons", ui::preferences::CONSTANTS_ROW),
+        ("SI prefixes for conversions", ui::preferences::SI_PREFIXES),
+    ];
+
+    let pref = ui::preferences::manager();
+    let mut items = Vec::new();
+    for (label, key) in options {
+        let checked = pref.get::<bool>(key).unwrap_or(false);
+        items.push(Item::new(menu_item_check(label.to_string(), checked, Message::ToggleOption(key))));
+    }
+    items.push(Item::with_menu(menu_item_sub("Result alignment".to_string(), Message::Null), menu_result_align()));
+    items.push(Item::with_menu(menu_item_sub("Number format".to_string(), Message::Null), menu_number_format()));
+    items.push(Item::with_menu(menu_item_sub("Decimal separator".to_string(), Message::Null), menu_decimal_separator()));
+    items.push(Item::with_menu(menu_item_sub("Decimal places".to_string(), Message::Null), menu_decimal_places()));
+    items.push(Item::with_menu(menu_item_sub("Fixed decimals".to_string(), Message::Null), menu_fixed_decimals()));
+    items.push(Item::with_menu(menu_item_sub("Pixel density".to_string(), Message::Null), menu_dpi()));
+    items.push(Item::with_menu(menu_item_sub("Save session".to_string(), Message::Null), menu_autosave()));
+    Menu::new(items).offset(3.0).spacing(2.0).max_width(180.0)
+
+}
+
+fn menu_result_align() -> Menu<'static, Message, Theme, Renderer> {
+
+    let current = ui::preferences::manager()
+        .get::<String>(ui::preferences::RESULT_ALIGN)
+        .unwrap_or("right".to_string());
+
+    let mut items = Vec::new();
+    for (label, value) in [("Left", "left"), ("Center", "center"), ("Right", "right")] {
+        items.push(Item::new(menu_item_check(
+            label.to_string(),
+            current == value,
+            Message::SetOption(ui::preferences::RESULT_ALIGN, value.to_string())
+        )));
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(100.0)
+
+}
+
+fn menu_number_format() -> Menu<'static, Message, Theme, Renderer> {
+
+    let current = ui::preferences::manager()
+        .get::<String>(ui::preferences::NUMBER_FORMAT)
+        .unwrap_or("auto".to_string());
+
+    let mut items = Vec::new();
+    for (label, value) in [("Auto", "auto"), ("Scientific", "scientific"),
+                           ("Engineering", "engineering"), ("Fixed", "fixed")] {
+        items.push(Item::new(menu_item_check(
+            label.to_string(),
+            current == value,
+            Message::SetOption(ui::preferences::NUMBER_FORMAT, value.to_string())
+        )));
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(100.0)
+
+}
+
+fn menu_decimal_separator() -> Menu<'static, Message, Theme, Renderer> {
+
+    let current = ui::preferences::manager()
+        .get::<String>(ui::preferences::DECIMAL_SEPARATOR)
+        .unwrap_or(".".to_string());
+
+    let mut items = Vec::new();
+    for (label, value) in [("Point (1.5)", "."), ("Comma (1,5)", ",")] {
+        items.push(Item::new(menu_item_check(
+            label.to_string(),
+            current == value,
+            Message::SetOption(ui::preferences::DECIMAL_SEPARATOR, value.to_string())
+        )));
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(120.0)
+
+}
+
+fn menu_decimal_places() -> Menu<'static, Message, Theme, Renderer> {
+
+    let current = ui::format::decimal_places();
+
+    let mut items = Vec::new();
+    for places in [0, 1, 2, 3, 4, 6, 8, 10, 12, 15] {
+        items.push(Item::new(menu_item_check(
+            places.to_string(),
+            current == places,
+            Message::SetOption(ui::preferences::DECIMAL_PLACES, places.to_string())
+        )));
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(100.0)
+
+}
+
+fn menu_fixed_decimals() -> Menu<'static, Message, Theme, Renderer> {
+
+    let current = ui::preferences::manager().get::<usize>(ui::preferences::FIXED_DECIMALS);
+
+    let mut items = Vec::new();
+    items.push(Item::new(menu_item_check(
+        "Off".to_string(),
+        current.is_none(),
+        Message::SetOption(ui::preferences::FIXED_DECIMALS, "off".to_string())
+    )));
+    for places in [1, 2, 3, 4, 6] {
+        items.push(Item::new(menu_item_check(
+            places.to_string(),
+            current == Some(places),
+            Message::SetOption(ui::preferences::FIXED_DECIMALS, places.to_string())
+        )));
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(100.0)
+
+}
+
+fn menu_dpi() -> Menu<'static, Message, Theme, Renderer> {
+
+    let current = conversions::length::dpi();
+
+    let mut items = Vec::new();
+    for dpi in [72.0, 96.0, 120.0, 144.0, 160.0, 240.0, 320.0] {
+        items.push(Item::new(menu_item_check(
+            format!("{} DPI", dpi),
+            current == dpi,
+            Message::SetOption(ui::preferences::DPI, dpi.to_string())
+        )));
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(100.0)
+
+}
+
+fn menu_input_figures() -> Menu<'static, Message, Theme, Renderer> {
+
+    let current = ui::preferences::manager().get::<usize>(ui::preferences::INPUT_FIGURES);
+
+    let mut items = Vec::new();
+    items.push(Item::new(menu_item_check(
+        "Off".to_string(),
+        current.is_none(),
+        Message::SetOption(ui::preferences::INPUT_FIGURES, "off".to_string())
+    )));
+    for figures in [2, 3, 4, 6] {
+        items.push(Item::new(menu_item_check(
+            format!("{} significant figures", figures),
+            current == Some(figures),
+            Message::SetOption(ui::preferences::INPUT_FIGURES, figures.to_string())
+        )));
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(160.0)
+
+}
+
+fn menu_autosave() -> Menu<'static, Message, Theme, Renderer> {
+
+    let current = ui::preferences::manager().get::<u64>(ui::preferences::AUTOSAVE_INTERVAL)
+        .unwrap_or(session::DEFAULT_INTERVAL);
+
+    let mut items = Vec::new();
+    for (label, seconds) in [("Never", 0), ("Every 30 seconds", 30), ("Every minute", 60), ("Every 5 minutes", 300)] {
+        items.push(Item::new(menu_item_check(
+            label.to_string(),
+            current == seconds,
+            Message::SetOption(ui::preferences::AUTOSAVE_INTERVAL, seconds.to_string())
+        )));
+    }
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(160.0)
+
+}
+
 fn menu_dimension()  -> Menu<'static, Message, Theme, Renderer> {
     let mut items = Vec::new();
+    if let Some(recent_menu) = menu_recent_conversions() {
+        items.push(Item::with_menu(menu_item_sub("Recent Conversions".to_string(), Message::Null), recent_menu));
+    }
+    let other_systems_only = ui::preferences::manager().get::<bool>(ui::preferences::OTHER_SYSTEMS_ONLY).unwrap_or(false);
+    items.push(Item::new(menu_item_check("Between systems only".to_string(), other_systems_only,
+                                         Message::ToggleOption(ui::preferences::OTHER_SYSTEMS_ONLY))));
+    items.push(Item::with_menu(menu_item_sub("Round input".to_string(), Message::Null), menu_input_figures()));
+    items.push(Item::new(menu_item("Slope".to_string(), Message::ToggleSlope)));
     for d in Dimension::iter() {
         items.push(Item::with_menu(menu_item(
             d.to_string(),
             Message::Null
         ), menu_unit_from(&d)));
     }
-    Menu::new(items).offset(3.0).spacing(2.0).max_width(90.0)
+    Menu::new(items).offset(3.0).spacing(2.0).max_width(140.0)
 
 }
 
+/// Recent conversions, most recent first, to repeat with one click.
+fn menu_recent_conversions() -> Option<Menu<'static, Message, Theme, Renderer>> {
+    let mut items = Vec::new();
+    if let Ok(entries) = recent::manager().recent().entries().read().as_deref() {
+        for c in entries.iter() {
+            if let (Some(from), Some(to)) = (conversions::find_unit(&c.from), conversions::find_unit(&c.to)) {
+                let label = format!("{} {} {} {}", c.input, from.symbol, ui::format::display_result(&c.output), to.symbol);
+                items.push(Item::new(menu_item(label, Message::RepeatConversion(from, to, c.input))));
+            }
+        }
+    }
+    if items.is_empty() {
+        return None;
+    }
+    Some(Menu::new(items).offset(0.0).spacing(2.0).max_width(250.0))
+}
+
 fn menu_unit_from(dimension: &Dimension)  -> Menu<'static, Message, Theme, Renderer> {
     let mut items = Vec::new();
     for unit in conversions::get_units(dimension).iter() {
         items.push(Item::with_menu(
-            menu_item_sub(unit.to_string(), Message::Null),
-            menu_unit_to(dimension, *unit)
+            menu_item_sub(ui::format::unit_label(unit), Message::Null),
+            menu_unit_to(*unit)
             ));
     }
-    Menu::new(items).offset(0.0).spacing(2.0).max_width(150.0)
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(190.0)
 
 }
 
-fn menu_unit_to(dimension: &Dimension, from: &'static Unit) -> Menu<'static, Message, Theme, Renderer> {
+fn menu_unit_to(from: &'static Unit) -> Menu<'static, Message, Theme, Renderer> {
+    let other_systems_only = ui::preferences::manager().get::<bool>(ui::preferences::OTHER_SYSTEMS_ONLY).unwrap_or(false);
     let mut items = Vec::new();
-    for unit in conversions::get_units(dimension).iter() {
+    for unit in conversions::get_to_units(from, other_systems_only).iter() {
         let to = unit;
         items.push(Item::new(
-            menu_item(unit.to_string(), Message::ConvertPerform(&from, *to)),
+            menu_item(ui::format::unit_label(unit), Message::ConvertPerform(&from, *to)),
         ));
     }
-    Menu::new(items).offset(0.0).spacing(2.0).max_width(140.0)
+    Menu::new(items).offset(0.0).spacing(2.0).max_width(180.0)
 }
 
 fn menu_item(label: String, msg: Message) -> Element<'static, Message> {
@@ -196,6 +522,19 @@ fn menu_item(label: String, msg: Message) -> Element<'static, Message> {
     menu_item_core(msg, content.into())
 }
 
+/// A menu item with a line of smaller text below the label.
+fn menu_item_detail(label: String, detail: String, msg: Message) -> Element<'static, Message> {
+    let content = column![
+                text(label)
+                    .width(Length::Fill)
+                    .shaping(Shaping::Advanced),
+                text(detail)
+                    .size(10)
+                    .width(Length::Fill),
+            ];
+    menu_item_core(msg, content.into())
+}
+
 fn menu_item_sub(label: String, msg: Message) -> Element<'static, Message> {
     let content = row![
                 text(label)
@@ -213,6 +552,41 @@ fn menu_item_sub(label: String, msg: Message) -> Element<'static, Message> {
     menu_item_core(msg, content.into())
 }
 
+fn menu_item_removable(label: String, msg: Message, remove_msg: Message) -> Element<'static, Message> {
+    let content = row![
+                menu_item(label, msg),
+                menu_item_core(remove_msg, text(iced_aw::bootstrap::icon_to_string(
+                    Bootstrap::X
+                ))
+                .font(BOOTSTRAP_FONT)
+                .width(Length::Shrink)
+                .align_y(alignment::Vertical::Center)
+                .into()),
+            ]
+        .align_y(iced::Alignment::Center);
+    content.into()
+}
+
+fn menu_item_check(label: String, checked: bool, msg: Message) -> Element<'static, Message> {
+    let check = if checked {
+        iced_aw::bootstrap::icon_to_string(Bootstrap::Check)
+    } else {
+        String::new()
+    };
+    let content = row![
+                text(check)
+                .font(BOOTSTRAP_FONT)
+                .width(Length::Fixed(16.0))
+                .align_y(alignment::Vertical::Center),
+                text(label)
+                    .width(Length::Fill)
+                    .shaping(Shaping::Advanced)
+                    .align_y(alignment::Vertical::Center),
+            ]
+        .align_y(iced::Alignment::Center);
+    menu_item_core(msg, content.into())
+}
+
 fn menu_item_core(msg: Message, content: Element<'static, Message>) -> Element<'static, Message> {
     Button::new(content)
         .style(|theme: &Theme, status| {
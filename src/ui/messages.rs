@@ -20,30 +20,189 @@
  *
  */
 
+use std::path::PathBuf;
+
 use iced::Theme;
-use iced::widget::text_editor::Action;
+use iced::widget::text_editor::{Action, Edit, Motion};
 
-use crate::conversions::Unit;
+use crate::conversions::{find_unit, Unit};
+use crate::conversions::slope::SlopeField;
+use crate::macros::Step;
+use crate::ui::format::CopyFormat;
 
 #[derive(Debug, Clone)]
 pub enum Message {
     WindowResized(f32, f32),
     WindowMoved(f32, f32),
     WindowClosed(),
+    AutoSave,
     EditorAction(Action),
     Char(String),
     Constant(String),
+    AddConstant,
+    RemoveConstant(String),
     Func(String),
+    Reciprocal,
+    Square,
+    Negate,
     History(String, f64),
+    HistoryFilter(String),
+    PreviousResult,
+    NextResult,
+    Bookmark(String),
+    AddBookmark,
+    RemoveBookmark(String),
     MoveRight,
     MoveLeft,
     MoveEnd,
     BackSpace,
     Clear,
     Copy(f64),
+    CopyAs(CopyFormat),
+    CopyExpression(String),
+    CopyConversionSentence,
     Evaluate,
     ToggleMode,
+    ToggleAngles,
+    ToggleSlope,
+    SlopeInput(SlopeField, String),
     ThemeChanged(Theme),
+    ToggleOption(&'static str),
+    SetOption(&'static str, String),
     ConvertPerform(&'static Unit, &'static Unit),
+    RepeatConversion(&'static Unit, &'static Unit, f64),
+    SwapUnits,
+    Paste,
+    FocusNext,
+    FocusPrevious,
+    StartRecording,
+    StopRecording,
+    RunMacro(String),
+    RemoveMacro(String),
+    MemoryAdd,
+    MemorySubtract,
+    MemoryRecall,
+    MemoryClear,
+    InsertConverted,
+    PinConversion(&'static Unit, &'static Unit),
+    UnpinConversion,
+    RunFile,
+    RunWorksheet(PathBuf),
+    ExportWorksheet,
+    WorksheetExported(Result<(), String>),
+    CloseWorksheet,
     Null,
-}
\ No newline at end of file
+}
+
+impl Message {
+    /// The step to record for this message when recording a macro, if it is one that can be
+    /// replayed. Typing in the editor is recorded as the characters typed. Messages that only
+    /// change options or the window, or that copy, aren't recorded.
+    pub(crate) fn to_step(&self) -> Option<Step> {
+        match self {
+            Message::Char(s) => Some(Step::Char(s.clone())),
+            Message::Constant(s) => Some(Step::Constant(s.clone())),
+            Message::Func(s) => Some(Step::Func(s.clone())),
+            Message::BackSpace => Some(Step::BackSpace),
+            Message::MoveLeft => Some(Step::MoveLeft),
+            Message::MoveRight => Some(Step::MoveRight),
+            Message::MoveEnd => Some(Step::MoveEnd),
+            Message::Clear => Some(Step::Clear),
+            Message::Evaluate => Some(Step::Evaluate),
+            Message::Reciprocal => Some(Step::Reciprocal),
+            Message::Square => Some(Step::Square),
+            Message::Negate => Some(Step::Negate),
+            Message::ToggleMode => Some(Step::ToggleMode),
+            Message::ConvertPerform(from, to) => Some(Step::Convert(from.name.to_string(), to.name.to_string())),
+            Message::SwapUnits => Some(Step::SwapUnits),
+            Message::InsertConverted => Some(Step::InsertConverted),
+            Message::MemoryAdd => Some(Step::MemoryAdd),
+            Message::MemorySubtract => Some(Step::MemorySubtract),
+            Message::MemoryRecall => Some(Step::MemoryRecall),
+            Message::MemoryClear => Some(Step::MemoryClear),
+            Message::EditorAction(action) => match action {
+                Action::Edit(Edit::Insert(c)) => Some(Step::Char(c.to_string())),
+                Action::Edit(Edit::Paste(text)) => Some(Step::Char(text.replace(['\n', '\r'], ""))),
+                Action::Edit(Edit::Enter) => Some(Step::Evaluate),
+                Action::Edit(Edit::Backspace) => Some(Step::BackSpace),
+                Action::Move(Motion::Left) => Some(Step::MoveLeft),
+                Action::Move(Motion::Right) => Some(Step::MoveRight),
+                Action::Move(Motion::End | Motion::DocumentEnd) => Some(Step::MoveEnd),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+impl Step {
+    /// The message to replay this step. A conversion between units that no longer exist can't
+    /// be replayed.
+    pub(crate) fn to_message(&self) -> Option<Message> {
+        Some(match self {
+            Step::Char(s) => Message::Char(s.clone()),
+            Step::Constant(s) => Message::Constant(s.clone()),
+            Step::Func(s) => Message::Func(s.clone()),
+            Step::BackSpace => Message::BackSpace,
+            Step::MoveLeft => Message::MoveLeft,
+            Step::MoveRight => Message::MoveRight,
+            Step::MoveEnd => Message::MoveEnd,
+            Step::Clear => Message::Clear,
+            Step::Evaluate => Message::Evaluate,
+            Step::Reciprocal => Message::Reciprocal,
+            Step::Square => Message::Square,
+            Step::Negate => Message::Negate,
+            Step::ToggleMode => Message::ToggleMode,
+            Step::Convert(from, to) => Message::ConvertPerform(find_unit(from)?, find_unit(to)?),
+            Step::SwapUnits => Message::SwapUnits,
+            Step::InsertConverted => Message::InsertConverted,
+            Step::MemoryAdd => Message::MemoryAdd,
+            Step::MemorySubtract => Message::MemorySubtract,
+            Step::MemoryRecall => Message::MemoryRecall,
+            Step::MemoryClear => Message::MemoryClear,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use iced::widget::text_editor::{Action, Edit, Motion};
+
+    use crate::conversions::length::{KILOMETRE, MILE};
+    use crate::macros::Step;
+    use crate::ui::messages::Message;
+
+    #[test]
+    fn test_record_replay() {
+        let messages = vec![
+            Message::Clear,
+            Message::Func("sqrt".to_string()),
+            Message::MoveLeft,
+            Message::EditorAction(Action::Edit(Edit::Insert('2'))),
+            Message::EditorAction(Action::Select(Motion::Left)),
+            Message::Copy(1.0),
+            Message::ConvertPerform(&KILOMETRE, &MILE),
+            Message::EditorAction(Action::Edit(Edit::Enter)),
+        ];
+        let steps: Vec<Step> = messages.iter().filter_map(Message::to_step).collect();
+        assert_eq!(steps, vec![
+            Step::Clear,
+            Step::Func("sqrt".to_string()),
+            Step::MoveLeft,
+            Step::Char("2".to_string()),
+            Step::Convert("Kilometre".to_string(), "Mile".to_string()),
+            Step::Evaluate,
+        ]);
+
+        let replayed: Vec<Step> = steps.iter()
+            .filter_map(Step::to_message)
+            .filter_map(|message| message.to_step())
+            .collect();
+        assert_eq!(replayed, steps);
+    }
+
+    #[test]
+    fn test_replay_unknown_unit() {
+        assert!(Step::Convert("Kilometre".to_string(), "Furlongs per fortnight".to_string()).to_message().is_none());
+    }
+}
@@ -30,6 +30,7 @@ use iced::theme::palette::{Background, Danger, Extended, Pair, Primary, Secondar
 pub(crate) mod calculator;
 pub(crate) mod messages;
 pub(crate) mod calc_window;
+pub(crate) mod format;
 pub(super) mod preferences;
 mod menu;
 
@@ -40,6 +40,36 @@ pub static APP_INFO: AppInfo = AppInfo {
 // Preference constants
 pub static ANGLE_MODE: &str = "angle-mode";
 pub static THEME: &str = "theme";
+pub static AUTO_EVALUATE: &str = "auto-evaluate";
+pub static PINNED_FROM: &str = "pinned-from";
+pub static PINNED_TO: &str = "pinned-to";
+pub static RESULT_ALIGN: &str = "result-align";
+pub static QUANTITIES: &str = "quantities";
+pub static DPI: &str = "dpi";
+pub static DECIMAL_MODE: &str = "decimal-mode";
+pub static DECIMAL_PLACES: &str = "decimal-places";
+pub static FIXED_DECIMALS: &str = "fixed-decimals";
+pub static GROUP_DIGITS: &str = "group-digits";
+pub static NUMBER_FORMAT: &str = "number-format";
+pub static DECIMAL_SEPARATOR: &str = "decimal-separator";
+pub static DMS_OUTPUT: &str = "dms-output";
+pub static NUMBER_WORDS: &str = "number-words";
+pub static MEMORY: &str = "memory";
+pub static VALIDATE_INPUT: &str = "validate-input";
+pub static CONSTANT_VALUES: &str = "constant-values";
+pub static PI_MULTIPLES: &str = "pi-multiples";
+pub static CONFIRM_CLEAR: &str = "confirm-clear";
+pub static SHOW_EXPRESSION: &str = "show-expression";
+pub static START_IN_CONVERT: &str = "start-in-convert";
+pub static DURATIONS: &str = "durations";
+pub static OTHER_SYSTEMS_ONLY: &str = "other-systems-only";
+pub static CONSTANTS_ROW: &str = "constants-row";
+pub static SI_PREFIXES: &str = "si-prefixes";
+pub static INTEGER_MODE: &str = "integer-mode";
+pub static INPUT_FIGURES: &str = "input-figures";
+pub static STRICT_PARENS: &str = "strict-parens";
+pub static RESULT_AS_EXPRESSION: &str = "result-becomes-expression";
+pub static AUTOSAVE_INTERVAL: &str = "autosave-interval";
 
 static MANAGER: LazyLock<PreferenceManager> = LazyLock::new( || {
     PreferenceManager {